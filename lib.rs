@@ -1,2 +0,0 @@
-pub mod loader;
-pub mod analyzer;
\ No newline at end of file
@@ -0,0 +1,671 @@
+// Module for analyzing product trends and relationships
+
+use petgraph::algo::kosaraju_scc;
+use petgraph::graph::NodeIndex;
+use petgraph::unionfind::UnionFind;
+use petgraph::visit::EdgeFiltered;
+use petgraph::Direction;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+
+use crate::centrality;
+use crate::community;
+use crate::paths::{self, PathError};
+use crate::stats::{self, GraphStats};
+use crate::loader::{
+    GraphMode, ParseMode, Product, ProductDataset, ProductGraph, ProductStatus, UnresolvedNodes,
+};
+
+// Which links 'top_products_by_connections' counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DegreeKind {
+    // Products listing this one as similar ("most recommended-to")
+    In,
+    // Products this one lists as similar
+    #[default]
+    Out,
+    // Both of the above
+    Total,
+}
+
+impl FromStr for DegreeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "in" => Ok(DegreeKind::In),
+            "out" => Ok(DegreeKind::Out),
+            "total" => Ok(DegreeKind::Total),
+            other => Err(format!("Unknown degree kind: {}", other)),
+        }
+    }
+}
+
+// Orders products that score the same: better (lower, known) sales rank first, then by ASIN
+fn tie_break(a: &Product, b: &Product) -> Ordering {
+    let rank = |p: &Product| if p.salesrank > 0 { p.salesrank } else { i32::MAX };
+    rank(a).cmp(&rank(b)).then_with(|| a.asin.cmp(&b.asin))
+}
+
+// A community found by 'detect_communities'
+#[derive(Debug, Clone)]
+pub struct TrendCommunity<'a> {
+    pub products: Vec<&'a Product>,     // Members the rankings consider, best sales rank first
+    pub size: usize,                    // Graph nodes in the community, placeholders included
+    pub dominant_group: Option<&'a str>, // Most common product group among the members
+    pub modularity: f64,                // The community's share of the partition's modularity
+}
+
+// Which links 'neighborhood' follows away from the center product
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HopDirection {
+    // Products the center lists as similar, and what they list in turn
+    Outgoing,
+    // Products listing the center as similar, and what lists those
+    Incoming,
+    // Links either way (the ego network)
+    #[default]
+    Both,
+}
+
+impl FromStr for HopDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "out" => Ok(HopDirection::Outgoing),
+            "in" => Ok(HopDirection::Incoming),
+            "both" => Ok(HopDirection::Both),
+            other => Err(format!("Unknown hop direction: {}", other)),
+        }
+    }
+}
+
+// The largest weakly connected component, see 'giant_component'
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GiantComponent<'a> {
+    pub nodes: usize,
+    pub edges: usize,                 // Links inside the component, see 'GraphMode::link_count'
+    pub node_share: f64,              // Fraction of all graph nodes inside the component
+    pub edge_share: f64,              // Fraction of all graph links inside the component
+    pub groups: Vec<(&'a str, usize)>, // Products per group, most common first
+}
+
+// Result of 'detect_communities'
+#[derive(Debug, Clone)]
+pub struct Communities<'a> {
+    pub modularity: f64, // Modularity of the whole partition, small communities included
+    pub communities: Vec<TrendCommunity<'a>>,
+}
+
+// Most common product group, ties going to the name that sorts first
+fn dominant_group<'a>(products: &[&'a Product]) -> Option<&'a str> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for product in products.iter().filter(|p| !p.group.is_empty()) {
+        *counts.entry(product.group.as_str()).or_default() += 1;
+    }
+    counts.into_iter()
+        .max_by(|(a_group, a), (b_group, b)| a.cmp(b).then_with(|| b_group.cmp(a_group)))
+        .map(|(group, _)| group)
+}
+
+// This struct is used to analyze product dataset relationships providing various insights
+pub struct ProductAnalyzer<'a> {
+    pub graph: &'a ProductGraph,               // Refers to product graph
+    pub products: &'a HashMap<String, Product>, // Refers to products map
+    pub graph_mode: GraphMode,                 // How the dataset built 'graph'
+    pub unresolved: UnresolvedNodes,           // Whether the dataset kept placeholder nodes
+    pub parse_mode: ParseMode,                 // How the dataset was parsed
+    pub include_discontinued: bool,            // Whether rankings consider discontinued records
+    pub max_salesrank: i32,                    // Worst sales rank still counted as an opportunity
+}
+
+impl<'a> ProductAnalyzer<'a> {
+    // Creates new analyzer for given dataset
+    pub fn new(dataset: &'a ProductDataset) -> Self {
+        Self {
+            graph: &dataset.graph,
+            products: &dataset.products,
+            graph_mode: dataset.graph_mode,
+            unresolved: dataset.unresolved,
+            parse_mode: dataset.parse_mode,
+            include_discontinued: false,
+            max_salesrank: 100_000,
+        }
+    }
+
+    // Discontinued records are left out of rankings unless asked for here
+    pub fn with_discontinued(mut self, include: bool) -> Self {
+        self.include_discontinued = include;
+        self
+    }
+
+    // Sets the sales rank cut-off used by 'find_low_competition_products'
+    pub fn with_max_salesrank(mut self, max_salesrank: i32) -> Self {
+        self.max_salesrank = max_salesrank;
+        self
+    }
+
+    /*
+    Number of links of the given kind at a node
+    Undirected and mutual graphs store each link both ways, so there every kind is
+    the plain number of linked products.
+    */
+    pub fn degree(&self, node: NodeIndex, kind: DegreeKind) -> usize {
+        let count = |direction| self.graph.neighbors_directed(node, direction).count();
+        match (self.graph_mode, kind) {
+            (GraphMode::Directed, DegreeKind::In) => count(Direction::Incoming),
+            (GraphMode::Directed, DegreeKind::Total) => {
+                count(Direction::Incoming) + count(Direction::Outgoing)
+            }
+            _ => count(Direction::Outgoing),
+        }
+    }
+
+    // Product behind a graph node, if it is one the rankings should consider
+    fn ranked_product(&self, node: NodeIndex) -> Option<&'a Product> {
+        self.products
+            .get(&self.graph[node])
+            .filter(|p| self.include_discontinued || p.status != ProductStatus::Discontinued)
+    }
+/*
+    Finds top products by number of connections
+    In directed graphs 'kind' picks incoming, outgoing or all links (see 'degree');
+    undirected graphs count links either way and mutual graphs only mutual links.
+    Ties go to the better sales rank, then to the lower ASIN.
+    Arguments:
+    'limit' - Maximum number of products to return
+    'kind' - Which links to count
+    Returns:
+    Vector of product references sorted by connection count
+*/
+    pub fn top_products_by_connections(&self, limit: usize, kind: DegreeKind) -> Vec<&'a Product> {
+        let mut products: Vec<_> = self.graph.node_indices()
+            .filter_map(|node| {
+                self.ranked_product(node).map(|p| (self.degree(node, kind), p))
+            })
+            .collect();
+        // Sort by connection count
+        products.sort_by(|(a_conn, a), (b_conn, b)| b_conn.cmp(a_conn).then_with(|| tie_break(a, b)));
+        // Return top N products
+        products.into_iter()
+            .take(limit)
+            .map(|(_, p)| p)
+            .collect()
+    }
+/*
+    Finds top products by weighted connections, where each similar-product link
+    counts with its strength (1 for the first listed product, 1/2 for the second...)
+    Follows outgoing links like 'DegreeKind::Out'; an undirected link
+    counts with the strength it was listed with.
+    Arguments:
+    'limit' - Maximum number of products to return
+    Returns:
+    Vector of (product, weighted connections) tuples, strongest first
+*/
+    pub fn top_products_by_weighted_connections(&self, limit: usize) -> Vec<(&'a Product, f32)> {
+        let mut products: Vec<_> = self.graph.node_indices()
+            .filter_map(|node| {
+                self.ranked_product(node).map(|p| {
+                    let strength: f32 = self.graph.edges(node).map(|e| e.weight().weight()).sum();
+                    (p, strength)
+                })
+            })
+            .collect();
+        products.sort_by(|(a, a_strength), (b, b_strength)| {
+            b_strength.total_cmp(a_strength).then_with(|| tie_break(a, b))
+        });
+        products.truncate(limit);
+        products
+    }
+/*
+    PageRank over the similar-products graph, so products many well-linked products
+    point to score highest. In directed graphs rank flows from a product to the ones
+    it lists; undirected and mutual graphs pass it both ways.
+    Arguments:
+    'damping' - Probability of following a link rather than jumping anywhere (usually 0.85)
+    'tolerance' - Stop once the scores change by less than this in total
+    'max_iter' - Upper bound on the number of iterations
+    Returns:
+    Score per graph node keyed by ASIN, summing to 1
+*/
+    pub fn pagerank(&self, damping: f64, tolerance: f64, max_iter: usize) -> HashMap<&'a str, f64> {
+        self.by_asin(centrality::pagerank(self.graph, damping, tolerance, max_iter))
+    }
+/*
+    Finds top products by PageRank, see 'pagerank'
+    Ties go to the better sales rank, then to the lower ASIN.
+    Arguments:
+    'limit' - Maximum number of products to return
+    'damping', 'tolerance', 'max_iter' - As for 'pagerank'
+    Returns:
+    Vector of (product, PageRank) pairs sorted by PageRank
+*/
+    pub fn top_products_by_pagerank(
+        &self,
+        limit: usize,
+        damping: f64,
+        tolerance: f64,
+        max_iter: usize,
+    ) -> Vec<(&'a Product, f64)> {
+        let scores = centrality::pagerank(self.graph, damping, tolerance, max_iter);
+        self.top_by_score(&scores, limit)
+    }
+
+/*
+    Betweenness centrality: how many shortest recommendation paths between other
+    products run through a product, which picks out "bridge" products between
+    otherwise separate parts of the graph. Paths follow links as listed in directed
+    graphs; in undirected and mutual graphs each pair is counted once.
+    Arguments:
+    'pivots' - None for exact scores (fine up to some thousand products), or the number
+               of sampled source products for an estimate on the full graph
+    Returns:
+    Score per graph node keyed by ASIN
+*/
+    pub fn betweenness(&self, pivots: Option<usize>) -> HashMap<&'a str, f64> {
+        self.by_asin(self.betweenness_scores(pivots))
+    }
+/*
+    Closeness centrality: how few hops a product needs to reach the rest of the
+    graph, scaled down for products that only reach part of it. Hops follow links as
+    listed in directed graphs and either way in undirected and mutual graphs.
+    Arguments:
+    'pivots' - None for exact scores, or the number of sampled target products
+    Returns:
+    Score per graph node keyed by ASIN, between 0 and 1
+*/
+    pub fn closeness(&self, pivots: Option<usize>) -> HashMap<&'a str, f64> {
+        self.by_asin(centrality::closeness(self.graph, pivots))
+    }
+/*
+    Finds bridge products by betweenness, see 'betweenness'
+    Ties go to the better sales rank, then to the lower ASIN.
+    Arguments:
+    'limit' - Maximum number of products to return
+    'pivots' - As for 'betweenness'
+    Returns:
+    Vector of (product, betweenness) pairs sorted by betweenness
+*/
+    pub fn top_products_by_betweenness(&self, limit: usize, pivots: Option<usize>) -> Vec<(&'a Product, f64)> {
+        self.top_by_score(&self.betweenness_scores(pivots), limit)
+    }
+/*
+    Finds the products closest to the rest of the graph, see 'closeness'
+    Ties go to the better sales rank, then to the lower ASIN.
+    Arguments:
+    'limit' - Maximum number of products to return
+    'pivots' - As for 'closeness'
+    Returns:
+    Vector of (product, closeness) pairs sorted by closeness
+*/
+    pub fn top_products_by_closeness(&self, limit: usize, pivots: Option<usize>) -> Vec<(&'a Product, f64)> {
+        self.top_by_score(&centrality::closeness(self.graph, pivots), limit)
+    }
+
+    // Betweenness per node, each pair counted once in undirected and mutual graphs
+    fn betweenness_scores(&self, pivots: Option<usize>) -> Vec<f64> {
+        let mut scores = centrality::betweenness(self.graph, pivots);
+        if self.graph_mode != GraphMode::Directed {
+            // Links are stored both ways, so every path was found from both ends
+            scores.iter_mut().for_each(|score| *score /= 2.0);
+        }
+        scores
+    }
+
+    // Keys per-node scores by the ASIN of their node
+    fn by_asin(&self, scores: Vec<f64>) -> HashMap<&'a str, f64> {
+        let graph = self.graph;
+        scores.into_iter()
+            .enumerate()
+            .map(|(i, score)| (graph[NodeIndex::new(i)].as_str(), score))
+            .collect()
+    }
+
+    // Ranked products with the highest per-node scores, ties broken by 'tie_break'
+    fn top_by_score(&self, scores: &[f64], limit: usize) -> Vec<(&'a Product, f64)> {
+        let mut products: Vec<_> = self.graph.node_indices()
+            .filter_map(|node| self.ranked_product(node).map(|p| (p, scores[node.index()])))
+            .collect();
+        products.sort_by(|(a, a_score), (b, b_score)| {
+            b_score.total_cmp(a_score).then_with(|| tie_break(a, b))
+        });
+        products.truncate(limit);
+        products
+    }
+/*
+    Detects product trend clusters using strongly connected components
+    In undirected and mutual graphs these are the connected components, since every
+    edge can be walked both ways.
+    arguments:
+    'min_size' - Minimum cluster size to include
+    Returns
+    Vector of product clusters
+*/
+    pub fn detect_trend_clusters(&self, min_size: usize) -> Vec<Vec<&'a Product>> {
+        kosaraju_scc(self.graph)
+            .into_iter()
+            .filter(|c| c.len() >= min_size) // Filter small clusters
+            .map(|cluster| {
+                // Convert node indices to products
+                cluster.iter()
+                    .filter_map(|n| self.ranked_product(*n))
+                    .collect()
+            })
+            .filter(|c: &Vec<_>| !c.is_empty()) // Remove Empty Clusters
+            .collect()
+    }
+/*
+    Like 'detect_trend_clusters', but only follows links of at least the given strength
+    Respects the graph mode the same way 'detect_trend_clusters' does.
+    arguments:
+    'min_size' - Minimum cluster size to include
+    'min_weight' - Weakest link kept, e.g. 0.5 keeps the top two similar products
+    Returns
+    Vector of product clusters
+*/
+    pub fn detect_strong_clusters(&self, min_size: usize, min_weight: f32) -> Vec<Vec<&'a Product>> {
+        let strong = EdgeFiltered::from_fn(self.graph, |e| e.weight().weight() >= min_weight);
+        kosaraju_scc(&strong)
+            .into_iter()
+            .filter(|c| c.len() >= min_size)
+            .map(|cluster| {
+                cluster.iter()
+                    .filter_map(|n| self.ranked_product(*n))
+                    .collect()
+            })
+            .filter(|c: &Vec<_>| !c.is_empty())
+            .collect()
+    }
+/*
+    Detects trend communities by maximising modularity (Louvain), as an alternative
+    to 'detect_trend_clusters': links are read as undirected whatever the graph mode,
+    so a community is a group of products that link to each other more than to the
+    rest, instead of one giant strongly connected component. Links listed both ways
+    weigh twice as much as one-way links in every graph mode; mutual graphs only
+    have the former.
+    Arguments:
+    'min_size' - Minimum community size (in graph nodes) to include
+    Returns:
+    Communities, largest first, with the modularity of the partition
+*/
+    pub fn detect_communities(&self, min_size: usize) -> Communities<'a> {
+        let partition = community::louvain(self.graph);
+        let mut members: Vec<Vec<NodeIndex>> = vec![Vec::new(); partition.community_count()];
+        for node in self.graph.node_indices() {
+            members[partition.membership[node.index()]].push(node);
+        }
+
+        let mut communities: Vec<_> = members.into_iter()
+            .zip(&partition.community_modularity)
+            .filter(|(nodes, _)| nodes.len() >= min_size)
+            .filter_map(|(nodes, &modularity)| {
+                let mut products: Vec<_> = nodes.iter().filter_map(|n| self.ranked_product(*n)).collect();
+                if products.is_empty() {
+                    return None;
+                }
+                products.sort_by(|a, b| tie_break(a, b));
+                Some(TrendCommunity {
+                    dominant_group: dominant_group(&products),
+                    size: nodes.len(),
+                    products,
+                    modularity,
+                })
+            })
+            .collect();
+        communities.sort_by(|a, b| {
+            b.size.cmp(&a.size).then_with(|| a.products[0].asin.cmp(&b.products[0].asin))
+        });
+
+        Communities { modularity: partition.modularity, communities }
+    }
+    /*
+    Finds products with low competition based on sales rank and cluster size
+    The cluster size is the 'DegreeKind::Out' connection count, so it depends on the
+    graph mode the same way.
+    Arguments:
+    'top_n' - Number of products to return
+    Returns:
+    Vector of (product, score) tuples sorted by opportunity score
+    */
+    pub fn find_low_competition_products(&self, top_n: usize) -> Vec<(&'a Product, f32)> {
+        let mut scores: Vec<(&Product, f32)> = self.graph.node_indices()
+            .filter_map(|node| {
+                let product = self.ranked_product(node)?;
+
+                // Filter out invalid sales ranks
+                if product.salesrank <= 0 || product.salesrank > self.max_salesrank {
+                    return None;
+                }
+
+                // Calculate opportunity score
+                let cluster_size = self.graph.neighbors(node).count();
+                let score = product.salesrank as f32 / (cluster_size as f32).max(1.0);
+
+                Some((product, score))
+            })
+            .collect();
+
+        // Sort by score (ascending - lower score = better opportunity)
+        scores.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        scores.into_iter().take(top_n).collect()
+    }
+
+/*
+    Weakly connected components: products linked through any chain of links, whatever
+    their direction. Unlike 'detect_trend_clusters' this includes every node, the
+    placeholders for ASINs without a record too.
+    Returns:
+    Node lists, largest component first (ties by lowest node index)
+*/
+    pub fn weakly_connected_components(&self) -> Vec<Vec<NodeIndex>> {
+        let labels = self.component_labels();
+        let mut components: Vec<Vec<NodeIndex>> = Vec::new();
+        let mut slot = HashMap::new();
+        for node in self.graph.node_indices() {
+            let index = *slot.entry(labels[node.index()]).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[index].push(node);
+        }
+        // Stable sort keeps components of equal size in order of their first node
+        components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+        components
+    }
+
+    // Number of weakly connected components of each size, smallest size first
+    pub fn component_size_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for component in self.weakly_connected_components() {
+            *histogram.entry(component.len()).or_default() += 1;
+        }
+        histogram
+    }
+
+/*
+    Summary of the largest weakly connected component: its share of the graph's nodes
+    and links and how its products split over product groups (all products, whether
+    or not the rankings consider them). Links are counted like 'GraphMode::link_count',
+    so a link stored both ways counts once. None for an empty graph.
+*/
+    pub fn giant_component(&self) -> Option<GiantComponent<'a>> {
+        let components = self.weakly_connected_components();
+        let giant = components.first()?;
+        let labels = self.component_labels();
+        let label = labels[giant[0].index()];
+        let edges = self.graph_mode.count_links(
+            self.graph.raw_edges().iter().filter(|e| labels[e.source().index()] == label),
+        );
+
+        let mut counts: HashMap<&'a str, usize> = HashMap::new();
+        for node in giant {
+            if let Some(product) = self.products.get(&self.graph[*node]) {
+                *counts.entry(product.group.as_str()).or_default() += 1;
+            }
+        }
+        let mut groups: Vec<_> = counts.into_iter().collect();
+        groups.sort_by(|(a_group, a), (b_group, b)| b.cmp(a).then_with(|| a_group.cmp(b_group)));
+
+        let share = |part: usize, whole: usize| if whole == 0 { 0.0 } else { part as f64 / whole as f64 };
+        Some(GiantComponent {
+            nodes: giant.len(),
+            edges,
+            node_share: share(giant.len(), self.graph.node_count()),
+            edge_share: share(edges, self.graph_mode.link_count(self.graph)),
+            groups,
+        })
+    }
+
+    // Weakly connected component label per node index
+    fn component_labels(&self) -> Vec<usize> {
+        let mut sets = UnionFind::new(self.graph.node_count());
+        for edge in self.graph.raw_edges() {
+            sets.union(edge.source().index(), edge.target().index());
+        }
+        sets.into_labeling()
+    }
+
+/*
+    Shortest recommendation chain from one product to another, following links the
+    way the graph mode stores them (only as listed in directed graphs)
+    Arguments:
+    'from', 'to' - ASINs of the two products, which need product records
+    Returns:
+    The products along the chain, both ends included, or why there is none
+*/
+    pub fn path_between(&self, from: &str, to: &str) -> Result<Vec<&'a Product>, PathError> {
+        let mut chains = self.k_shortest_paths(from, to, 1)?;
+        Ok(chains.remove(0))
+    }
+/*
+    Up to 'k' shortest loopless recommendation chains, shortest first, see 'path_between'
+    Chains may pass through discontinued products but never through placeholder nodes:
+    in undirected graphs those do get links both ways, but no product actually lists
+    a placeholder's neighbors.
+*/
+    pub fn k_shortest_paths(&self, from: &str, to: &str, k: usize) -> Result<Vec<Vec<&'a Product>>, PathError> {
+        let source = self.product_node(from)?;
+        let target = self.product_node(to)?;
+        let placeholders: HashSet<NodeIndex> = self.graph.node_indices()
+            .filter(|node| !self.products.contains_key(&self.graph[*node]))
+            .collect();
+        let chains: Vec<Vec<&'a Product>> = paths::k_shortest_paths(self.graph, source, target, k, &placeholders)
+            .into_iter()
+            .map(|path| path.into_iter().map(|node| &self.products[&self.graph[node]]).collect())
+            .collect();
+        if chains.is_empty() && k > 0 {
+            return Err(PathError::NoPath { from: from.to_string(), to: to.to_string() });
+        }
+        Ok(chains)
+    }
+
+/*
+    Extracts the neighborhood of a product as a standalone dataset, so every other
+    analysis can run on just that part of the market
+    Arguments:
+    'asin' - The center product, which needs a product record
+    'hops' - How many links away from the center to go (0 is the product alone)
+    'direction' - Which links to follow; in undirected and mutual graphs they all agree
+    Returns:
+    The subgraph induced by the products within reach, with their product records,
+    categories and ids, or None for an unknown ASIN. Nodes and edges keep their
+    relative order. The result has no source hash, so its snapshots never pass for a
+    cache of the full dump.
+*/
+    pub fn neighborhood(&self, asin: &str, hops: usize, direction: HopDirection) -> Option<ProductDataset> {
+        let center = self.product_node(asin).ok()?;
+        let mut within = HashSet::from([center]);
+        let mut frontier = vec![center];
+        for _ in 0..hops {
+            let mut next = Vec::new();
+            for node in frontier {
+                let outgoing = self.graph.neighbors_directed(node, Direction::Outgoing);
+                let incoming = self.graph.neighbors_directed(node, Direction::Incoming);
+                let neighbors: Vec<NodeIndex> = match direction {
+                    HopDirection::Outgoing => outgoing.collect(),
+                    HopDirection::Incoming => incoming.collect(),
+                    HopDirection::Both => outgoing.chain(incoming).collect(),
+                };
+                next.extend(neighbors.into_iter().filter(|n| within.insert(*n)));
+            }
+            frontier = next;
+        }
+
+        let graph = self.graph.filter_map(
+            |node, asin| within.contains(&node).then(|| asin.clone()),
+            |_, link| Some(*link),
+        );
+        let mut dataset = ProductDataset {
+            graph_mode: self.graph_mode,
+            unresolved: self.unresolved,
+            parse_mode: self.parse_mode,
+            ..Default::default()
+        };
+        for node in graph.node_indices() {
+            let Some(product) = self.products.get(&graph[node]) else { continue };
+            dataset.categories.insert_product(product);
+            if let Some(id) = product.id {
+                dataset.ids.insert(id, &product.asin);
+            }
+            if product.status == ProductStatus::Discontinued {
+                dataset.discontinued += 1;
+            }
+            dataset.products.insert(product.asin.clone(), product.clone());
+        }
+        dataset.graph = graph;
+        Some(dataset)
+    }
+
+    // Graph node of a product with a record
+    fn product_node(&self, asin: &str) -> Result<NodeIndex, PathError> {
+        self.graph.node_indices()
+            .find(|node| self.graph[*node] == asin && self.products.contains_key(asin))
+            .ok_or_else(|| PathError::UnknownProduct(asin.to_string()))
+    }
+
+    // Degree distributions, density, clustering, diameter and more, see 'GraphStats'
+    pub fn graph_stats(&self) -> GraphStats {
+        stats::graph_stats(self.graph, self.graph_mode)
+    }
+
+    //Print dataset statistics
+    pub fn print_stats(&self) {
+        let stats = self.graph_stats();
+        println!("\nDataset Statistics:");
+        let unresolved = self.graph.node_indices()
+            .filter(|node| !self.products.contains_key(&self.graph[*node]))
+            .count();
+        println!("- Products: {}", stats.nodes - unresolved);
+        println!("- Unresolved placeholder nodes: {}", unresolved);
+        println!("- Graph: {:?}", stats.graph_mode);
+        println!("- Connections: {}", stats.links);
+        println!("- Avg connections per product: {:.2}", stats.avg_degree);
+        println!("- Max degree (in / out / total): {} / {} / {}",
+            stats.max_in_degree, stats.max_out_degree, stats.max_total_degree);
+        println!("- Density: {:.6}", stats.density);
+        println!("- Reciprocity: {:.3}", stats.reciprocity);
+        println!("- Clustering (global / average): {:.3} / {:.3}", stats.global_clustering, stats.avg_clustering);
+        println!("- Diameter: {}{}", if stats.diameter_exact { "" } else { ">= " }, stats.diameter);
+        println!("- Self-loops: {}", stats.self_loops);
+        println!("- Dangling nodes: {}", stats.dangling_nodes);
+
+        let histogram = self.component_size_histogram();
+        println!("- Weakly connected components: {}", histogram.values().sum::<usize>());
+        println!("- Component sizes (size: count):");
+        for (size, count) in histogram.iter().rev().take(10) {
+            println!("  {}: {}", size, count);
+        }
+        if let Some(giant) = self.giant_component() {
+            println!("- Giant component: {} nodes ({:.1}%), {} links ({:.1}%)",
+                giant.nodes, giant.node_share * 100.0, giant.edges, giant.edge_share * 100.0);
+            println!("- Giant component groups:");
+            for (group, count) in giant.groups.iter().take(10) {
+                let group = if group.is_empty() { "unknown" } else { group };
+                println!("  {}: {}", group, count);
+            }
+        }
+    }
+}
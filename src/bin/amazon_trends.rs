@@ -0,0 +1,563 @@
+// Main module for Amazon Product Trend Analyzer
+use std::process;
+use amazon_trends::analyzer::{DegreeKind, HopDirection, ProductAnalyzer};
+use amazon_trends::loader::{
+    self, GraphMode, LoadOptions, LoaderError, ParseMode, Product, ProductDataset, UnresolvedNodes,
+};
+use amazon_trends::output::{self, OutputFormat, ProductSummary, StatsSummary};
+use amazon_trends::snapshot;
+use petgraph::Direction;
+use serde::Serialize;
+
+const USAGE: &str = "\
+Usage: amazon_trends <COMMAND> [OPTIONS]
+
+Commands:
+  stats                       Print dataset statistics
+  top                         Products with the most connections
+  influence                   Products with the highest PageRank
+  bridges                     Products with the highest betweenness centrality
+  closeness                   Products with the highest closeness centrality
+  clusters                    Trend clusters (strongly connected components)
+  communities                 Trend communities by modularity (Louvain)
+  opportunities               Low competition products
+  product <ASIN>              Details for a single product
+  path <FROM> <TO>            Shortest recommendation chains between two products
+
+Options:
+  -i, --input <PATH>          Dataset file [default: data/amazon-meta.txt]
+  -n, --limit <N>             Number of results to show [default: 5, clusters: 3, path: 1]
+      --degree <KIND>         Links top counts: in (most recommended-to), out or total [default: in]
+      --weighted              Rank top by link strength (1 / position in the similar list) instead
+      --damping <D>           PageRank damping factor for influence, 0 to 1 [default: 0.85]
+      --pivots <N>            Sampled products for bridges and closeness [default: exact up to 5000 nodes, else 256]
+      --min-size <N>          Minimum cluster or community size [default: 5]
+      --min-strength <W>      Weakest link clusters follow, 1 = first similar product only [default: 0]
+      --max-rank <N>          Worst sales rank counted as an opportunity [default: 100000]
+      --include-discontinued  Keep discontinued products in rankings
+      --graph <MODE>          Read similar links as directed, undirected or mutual [default: directed]
+      --prune-unresolved      Drop graph nodes for similar ASINs that have no record
+      --around <ASIN>         Run the command on the neighborhood of one product only
+      --hops <N>              Links away from the --around product to include [default: 1]
+      --direction <DIR>       Links --around follows: out, in or both [default: both]
+      --strict                Fail on the first malformed line instead of skipping it
+  -j, --threads <N>           Threads used to parse the dump [default: number of CPUs]
+  -f, --format <FORMAT>       text, json, jsonl or csv [default: text]
+      --cache <PATH>          Binary snapshot to load from, or to create when missing or stale
+  -h, --help                  Print this help";
+
+// PageRank convergence settings for the influence command
+const PAGERANK_TOLERANCE: f64 = 1e-6;
+const PAGERANK_MAX_ITER: usize = 100;
+
+// Largest graph the bridges command scores exactly, and the sample size beyond that
+const EXACT_CENTRALITY_NODES: usize = 5_000;
+const DEFAULT_PIVOTS: usize = 256;
+
+// Which analysis to run
+enum Command {
+    Stats,
+    Top,
+    Influence,
+    Bridges,
+    Closeness,
+    Clusters,
+    Communities,
+    Opportunities,
+    Product(String),
+    Path(String, String),
+}
+
+// Parsed command line
+struct Cli {
+    command: Command,
+    input: String,
+    limit: Option<usize>,
+    degree: DegreeKind,
+    weighted: bool,
+    damping: f64,
+    pivots: Option<usize>,
+    min_size: usize,
+    min_strength: f32,
+    max_rank: i32,
+    include_discontinued: bool,
+    strict: bool,
+    graph_mode: GraphMode,
+    prune_unresolved: bool,
+    around: Option<String>,       // Center product of the neighborhood to analyze instead
+    hops: usize,
+    direction: HopDirection,
+    threads: usize,
+    format: Option<OutputFormat>, // None prints the human readable report
+    cache: Option<String>,
+}
+
+// Main function
+fn main() {
+    let cli = match parse_args(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if cli.format.is_none() {
+        println!("Amazon Product Trend Analyzer");
+        println!("----------------------------");
+    }
+
+    // Loading dataset
+    let dataset = match load_dataset(&cli) {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("Fatal error: {}", e);
+            process::exit(1);
+        }
+    };
+    let dataset = match &cli.around {
+        Some(asin) => match ProductAnalyzer::new(&dataset).neighborhood(asin, cli.hops, cli.direction) {
+            Some(neighborhood) => neighborhood,
+            None => {
+                eprintln!("No product with ASIN {}", asin);
+                process::exit(1);
+            }
+        },
+        None => dataset,
+    };
+
+    let analyzer = ProductAnalyzer::new(&dataset)
+        .with_discontinued(cli.include_discontinued)
+        .with_max_salesrank(cli.max_rank);
+
+    let Some(format) = cli.format else {
+        match &cli.command {
+            Command::Stats => analyzer.print_stats(),
+            Command::Top if cli.weighted => analyze_strength(&analyzer, cli.limit.unwrap_or(5)),
+            Command::Top => analyze_bestsellers(&analyzer, cli.limit.unwrap_or(5), cli.degree),
+            Command::Influence => analyze_influence(&analyzer, cli.limit.unwrap_or(5), cli.damping),
+            Command::Bridges => analyze_bridges(&analyzer, cli.limit.unwrap_or(5), pivots(&cli, &dataset)),
+            Command::Closeness => analyze_closeness(&analyzer, cli.limit.unwrap_or(5), pivots(&cli, &dataset)),
+            Command::Clusters => analyze_trends(&analyzer, &cli, cli.limit.unwrap_or(3)),
+            Command::Communities => analyze_communities(&analyzer, cli.min_size, cli.limit.unwrap_or(3)),
+            Command::Opportunities => analyze_competition(&analyzer, cli.limit.unwrap_or(5)),
+            Command::Product(asin) => describe_product(&dataset, asin),
+            Command::Path(from, to) => describe_paths(&analyzer, from, to, cli.limit.unwrap_or(1)),
+        }
+        return;
+    };
+
+    let written = match &cli.command {
+        Command::Stats => {
+            let stats = output::dataset_stats(&dataset, &analyzer);
+            // CSV can't hold the histograms, so it gets the flat summary
+            if format == OutputFormat::Csv {
+                emit(&[StatsSummary::from(&stats)], format)
+            } else {
+                emit(&[stats], format)
+            }
+        }
+        Command::Top if cli.weighted => {
+            let top = analyzer.top_products_by_weighted_connections(cli.limit.unwrap_or(5));
+            emit(&output::scored_products(&top), format)
+        }
+        Command::Top => {
+            let top = analyzer.top_products_by_connections(cli.limit.unwrap_or(5), cli.degree);
+            emit(&output::ranked_products(&top), format)
+        }
+        Command::Influence => {
+            let top = analyzer.top_products_by_pagerank(
+                cli.limit.unwrap_or(5), cli.damping, PAGERANK_TOLERANCE, PAGERANK_MAX_ITER);
+            emit(&output::scored_products(&top), format)
+        }
+        Command::Bridges => {
+            let top = analyzer.top_products_by_betweenness(cli.limit.unwrap_or(5), pivots(&cli, &dataset));
+            emit(&output::scored_products(&top), format)
+        }
+        Command::Closeness => {
+            let top = analyzer.top_products_by_closeness(cli.limit.unwrap_or(5), pivots(&cli, &dataset));
+            emit(&output::scored_products(&top), format)
+        }
+        Command::Clusters => {
+            let mut clusters = trend_clusters(&analyzer, &cli);
+            clusters.truncate(cli.limit.unwrap_or(3));
+            emit(&output::cluster_members(&clusters), format)
+        }
+        Command::Communities => {
+            let mut found = analyzer.detect_communities(cli.min_size);
+            found.communities.truncate(cli.limit.unwrap_or(3));
+            emit(&output::community_members(&found.communities), format)
+        }
+        Command::Opportunities => {
+            let scored = analyzer.find_low_competition_products(cli.limit.unwrap_or(5));
+            emit(&output::opportunities(&scored), format)
+        }
+        Command::Product(asin) => match dataset.products.get(asin) {
+            // CSV can't hold the nested category and review lists, so it gets the flat summary
+            Some(product) if format == OutputFormat::Csv => emit(&[ProductSummary::from(product)], format),
+            Some(product) => emit(&[product], format),
+            None => {
+                eprintln!("No product with ASIN {}", asin);
+                process::exit(1);
+            }
+        },
+        Command::Path(from, to) => match analyzer.k_shortest_paths(from, to, cli.limit.unwrap_or(1)) {
+            Ok(paths) => emit(&output::path_steps(&paths), format),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+    };
+
+    if let Err(e) = written {
+        eprintln!("Failed to write output: {}", e);
+        process::exit(1);
+    }
+}
+
+// Writes result rows to stdout in a machine-readable format
+fn emit<T: Serialize>(rows: &[T], format: OutputFormat) -> std::io::Result<()> {
+    output::write_rows(rows, format, std::io::stdout().lock())
+}
+
+// Turns the raw arguments into a 'Cli', or an error message for the user
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Cli, String> {
+    let mut command = None;
+    let mut cli = Cli {
+        command: Command::Stats,
+        input: "data/amazon-meta.txt".to_string(),
+        limit: None,
+        degree: DegreeKind::In,
+        weighted: false,
+        damping: 0.85,
+        pivots: None,
+        min_size: 5,
+        min_strength: 0.0,
+        max_rank: 100_000,
+        include_discontinued: false,
+        strict: false,
+        graph_mode: GraphMode::Directed,
+        prune_unresolved: false,
+        around: None,
+        hops: 1,
+        direction: HopDirection::Both,
+        threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        format: None,
+        cache: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next().ok_or_else(|| format!("Missing value for {}", flag))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            "-i" | "--input" => cli.input = value(&arg)?,
+            "-n" | "--limit" => cli.limit = Some(parse_number(&arg, &value(&arg)?)?),
+            "--degree" => cli.degree = value(&arg)?.parse()?,
+            "--weighted" => cli.weighted = true,
+            "--damping" => {
+                cli.damping = parse_number(&arg, &value(&arg)?)?;
+                if !(0.0..=1.0).contains(&cli.damping) {
+                    return Err(format!("--damping must be between 0 and 1, got {}", cli.damping));
+                }
+            }
+            "--pivots" => cli.pivots = Some(parse_number(&arg, &value(&arg)?)?),
+            "--min-size" => cli.min_size = parse_number(&arg, &value(&arg)?)?,
+            "--min-strength" => cli.min_strength = parse_number(&arg, &value(&arg)?)?,
+            "--max-rank" => cli.max_rank = parse_number(&arg, &value(&arg)?)?,
+            "--include-discontinued" => cli.include_discontinued = true,
+            "--strict" => cli.strict = true,
+            "--prune-unresolved" => cli.prune_unresolved = true,
+            "--graph" => cli.graph_mode = value(&arg)?.parse()?,
+            "--around" => cli.around = Some(value(&arg)?),
+            "--hops" => cli.hops = parse_number(&arg, &value(&arg)?)?,
+            "--direction" => cli.direction = value(&arg)?.parse()?,
+            "-j" | "--threads" => cli.threads = parse_number(&arg, &value(&arg)?)?,
+            "--cache" => cli.cache = Some(value(&arg)?),
+            "-f" | "--format" => {
+                let format = value(&arg)?;
+                cli.format = match format.as_str() {
+                    "text" => None,
+                    other => Some(other.parse()?),
+                };
+            }
+            flag if flag.starts_with('-') => return Err(format!("Unknown option: {}", flag)),
+            name if command.is_none() => {
+                command = Some(match name {
+                    "stats" => Command::Stats,
+                    "top" => Command::Top,
+                    "influence" => Command::Influence,
+                    "bridges" => Command::Bridges,
+                    "closeness" => Command::Closeness,
+                    "clusters" => Command::Clusters,
+                    "communities" => Command::Communities,
+                    "opportunities" => Command::Opportunities,
+                    "product" => Command::Product(value("product")?),
+                    "path" => Command::Path(value("path")?, value("path")?),
+                    other => return Err(format!("Unknown command: {}", other)),
+                });
+            }
+            extra => return Err(format!("Unexpected argument: {}", extra)),
+        }
+    }
+
+    cli.command = command.ok_or("No command given")?;
+    // Zero chains would print a bare header and report success
+    if matches!(cli.command, Command::Path(..)) && cli.limit == Some(0) {
+        return Err("--limit must be at least 1 for path".to_string());
+    }
+    Ok(cli)
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid number for {}: {}", flag, value))
+}
+
+// Loads dataset from the path given on the command line
+fn load_dataset(cli: &Cli) -> Result<ProductDataset, LoaderError> {
+    let mode = if cli.strict { ParseMode::Strict } else { ParseMode::Lenient };
+    let options = LoadOptions {
+        mode,
+        graph_mode: cli.graph_mode,
+        unresolved: if cli.prune_unresolved { UnresolvedNodes::Prune } else { UnresolvedNodes::Keep },
+        quiet: cli.format.is_some(),
+        threads: cli.threads,
+    };
+    if let Some(cache) = &cli.cache {
+        return snapshot::load_cached(&cli.input, cache, &options);
+    }
+    let (dataset, _report) = loader::load_dataset_with_options(&cli.input, &options)?;
+    Ok(dataset)
+}
+
+// Analyzes and prints top products by connections
+fn analyze_bestsellers(analyzer: &ProductAnalyzer, limit: usize, degree: DegreeKind) {
+    let top_products = analyzer.top_products_by_connections(limit, degree);
+
+    if top_products.is_empty() {
+        println!("No products found with connections.");
+        return;
+    }
+
+    println!("\nTop {} Best-Selling Products:", top_products.len());
+    for (i, product) in top_products.iter().enumerate() {
+        println!("{}. {}", i + 1, product.title);
+        println!("   - ASIN: {}", product.asin);
+        println!("   - Category: {}", product.group);
+        println!("   - Sales Rank: {}", product.salesrank);
+    }
+}
+
+// Trend clusters, following only strong enough links when --min-strength is given
+fn trend_clusters<'a>(analyzer: &ProductAnalyzer<'a>, cli: &Cli) -> Vec<Vec<&'a Product>> {
+    if cli.min_strength > 0.0 {
+        analyzer.detect_strong_clusters(cli.min_size, cli.min_strength)
+    } else {
+        analyzer.detect_trend_clusters(cli.min_size)
+    }
+}
+
+// Analyzes and prints top products by the strength of their similar links
+fn analyze_strength(analyzer: &ProductAnalyzer, limit: usize) {
+    let top_products = analyzer.top_products_by_weighted_connections(limit);
+
+    if top_products.is_empty() {
+        println!("No products found with connections.");
+        return;
+    }
+
+    println!("\nTop {} Products by Link Strength:", top_products.len());
+    for (i, (product, strength)) in top_products.iter().enumerate() {
+        println!("{}. {}", i + 1, product.title);
+        println!("   - ASIN: {}", product.asin);
+        println!("   - Category: {}", product.group);
+        println!("   - Sales Rank: {}", product.salesrank);
+        println!("   - Link Strength: {:.2}", strength);
+    }
+}
+
+// Analyzes and prints the most influential products by PageRank
+fn analyze_influence(analyzer: &ProductAnalyzer, limit: usize, damping: f64) {
+    let top_products = analyzer.top_products_by_pagerank(limit, damping, PAGERANK_TOLERANCE, PAGERANK_MAX_ITER);
+
+    if top_products.is_empty() {
+        println!("No products found.");
+        return;
+    }
+
+    println!("\nTop {} Most Influential Products:", top_products.len());
+    for (i, (product, score)) in top_products.iter().enumerate() {
+        println!("{}. {}", i + 1, product.title);
+        println!("   - ASIN: {}", product.asin);
+        println!("   - Category: {}", product.group);
+        println!("   - Sales Rank: {}", product.salesrank);
+        println!("   - PageRank: {:.6}", score);
+    }
+}
+
+// Betweenness is exact on small graphs and sampled on big ones unless --pivots says otherwise
+fn pivots(cli: &Cli, dataset: &ProductDataset) -> Option<usize> {
+    cli.pivots.or((dataset.graph.node_count() > EXACT_CENTRALITY_NODES).then_some(DEFAULT_PIVOTS))
+}
+
+// Analyzes and prints bridge products by betweenness centrality
+fn analyze_bridges(analyzer: &ProductAnalyzer, limit: usize, pivots: Option<usize>) {
+    let top_products = analyzer.top_products_by_betweenness(limit, pivots);
+
+    if top_products.is_empty() {
+        println!("No products found.");
+        return;
+    }
+
+    match pivots {
+        Some(k) => println!("\nTop {} Bridge Products (sampled from {} sources):", top_products.len(), k),
+        None => println!("\nTop {} Bridge Products:", top_products.len()),
+    }
+    for (i, (product, score)) in top_products.iter().enumerate() {
+        println!("{}. {}", i + 1, product.title);
+        println!("   - ASIN: {}", product.asin);
+        println!("   - Category: {}", product.group);
+        println!("   - Sales Rank: {}", product.salesrank);
+        println!("   - Betweenness: {:.1}", score);
+    }
+}
+
+// Analyzes and prints the products closest to the rest of the graph
+fn analyze_closeness(analyzer: &ProductAnalyzer, limit: usize, pivots: Option<usize>) {
+    let top_products = analyzer.top_products_by_closeness(limit, pivots);
+
+    if top_products.is_empty() {
+        println!("No products found.");
+        return;
+    }
+
+    match pivots {
+        Some(k) => println!("\nTop {} Central Products (sampled from {} targets):", top_products.len(), k),
+        None => println!("\nTop {} Central Products:", top_products.len()),
+    }
+    for (i, (product, score)) in top_products.iter().enumerate() {
+        println!("{}. {}", i + 1, product.title);
+        println!("   - ASIN: {}", product.asin);
+        println!("   - Category: {}", product.group);
+        println!("   - Sales Rank: {}", product.salesrank);
+        println!("   - Closeness: {:.4}", score);
+    }
+}
+
+// Analyzes and prints product trend clusters
+fn analyze_trends(analyzer: &ProductAnalyzer, cli: &Cli, limit: usize) {
+    let clusters = trend_clusters(analyzer, cli);
+
+    if clusters.is_empty() {
+        println!("No trend clusters found.");
+        return;
+    }
+
+    println!("\nEmerging Product Trends:");
+    for (i, cluster) in clusters.iter().take(limit).enumerate() {
+        println!("Trend Group {} ({} products):", i + 1, cluster.len());
+        println!("Sample Products:");
+        for product in cluster.iter().take(3) {
+            println!("- {} (Rank: {})", product.title, product.salesrank);
+        }
+    }
+}
+
+// Analyzes and prints modularity-based trend communities
+fn analyze_communities(analyzer: &ProductAnalyzer, min_size: usize, limit: usize) {
+    let found = analyzer.detect_communities(min_size);
+
+    if found.communities.is_empty() {
+        println!("No trend communities found.");
+        return;
+    }
+
+    println!("\nTrend Communities (modularity {:.3}):", found.modularity);
+    for (i, community) in found.communities.iter().take(limit).enumerate() {
+        println!("Community {} ({} products, mostly {}):",
+            i + 1, community.size, community.dominant_group.unwrap_or("unknown"));
+        println!("Sample Products:");
+        for product in community.products.iter().take(3) {
+            println!("- {} (Rank: {})", product.title, product.salesrank);
+        }
+    }
+}
+
+// Analyzes and prints low competition products
+fn analyze_competition(analyzer: &ProductAnalyzer, limit: usize) {
+    let opportunities = analyzer.find_low_competition_products(limit);
+
+    if opportunities.is_empty() {
+        println!("No low competition products found.");
+        return;
+    }
+
+    println!("\nBest Market Opportunities:");
+    for (i, (product, score)) in opportunities.iter().enumerate() {
+        println!("{}. {} (Opportunity Score: {:.2})", i + 1, product.title, score);
+        println!("   - Current Rank: {}", product.salesrank);
+        println!("   - Category: {}", product.group);
+    }
+}
+
+// Prints the shortest recommendation chains between two products
+fn describe_paths(analyzer: &ProductAnalyzer, from: &str, to: &str, limit: usize) {
+    let paths = match analyzer.k_shortest_paths(from, to, limit) {
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    println!("\nRecommendation Chains from {} to {}:", from, to);
+    for (i, path) in paths.iter().enumerate() {
+        println!("{}. {} steps", i + 1, path.len() - 1);
+        for product in path {
+            println!("   - {} ({}, {})", product.title, product.asin, product.group);
+        }
+    }
+}
+
+// Prints everything the dataset knows about one product
+fn describe_product(dataset: &ProductDataset, asin: &str) {
+    let Some(product) = dataset.products.get(asin) else {
+        eprintln!("No product with ASIN {}", asin);
+        process::exit(1);
+    };
+
+    println!("\n{}", product.title);
+    println!("- ASIN: {}", product.asin);
+    if let Some(id) = product.id {
+        println!("- Id: {}", id);
+    }
+    println!("- Status: {:?}", product.status);
+    println!("- Category: {}", product.group);
+    println!("- Sales Rank: {}", product.salesrank);
+    println!("- Similar products listed: {}", product.similar.len());
+
+    let node = dataset.graph.node_indices().find(|n| dataset.graph[*n] == product.asin);
+    if let Some(node) = node {
+        println!("- Links out: {}", dataset.graph.neighbors_directed(node, Direction::Outgoing).count());
+        println!("- Links in: {}", dataset.graph.neighbors_directed(node, Direction::Incoming).count());
+        let reciprocal = dataset.graph.edges(node).filter(|e| e.weight().reciprocal).count();
+        println!("- Reciprocal links: {}", reciprocal);
+    }
+
+    let summary = &product.review_summary;
+    println!("- Reviews: {} total, {} downloaded, {:.1} avg rating",
+        summary.total, summary.downloaded, summary.avg_rating);
+
+    let breadcrumbs = dataset.categories.breadcrumbs(&product.asin);
+    if !breadcrumbs.is_empty() {
+        println!("- Categories:");
+        for crumb in breadcrumbs {
+            println!("  {}", crumb);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+// Module for centrality measures over the product graph
+// Scores are returned per node, indexed by 'NodeIndex::index()'
+
+use petgraph::graph::NodeIndex;
+use petgraph::Direction;
+use std::collections::VecDeque;
+
+use crate::loader::ProductGraph;
+
+// Fixed seed so sampled scores come out the same on every run
+const PIVOT_SEED: u64 = 0x5eed_1234_abcd_ef01;
+
+/*
+    PageRank by power iteration
+    Products without outgoing links spread their rank evenly over all products, so
+    the scores always sum to 1. Stops after 'max_iter' rounds or once the scores
+    move by less than 'tolerance' in total (L1 distance) between two rounds.
+*/
+pub fn pagerank(graph: &ProductGraph, damping: f64, tolerance: f64, max_iter: usize) -> Vec<f64> {
+    let n = graph.node_count();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let out_degree: Vec<usize> = graph.node_indices().map(|v| graph.neighbors(v).count()).collect();
+    let mut rank = vec![1.0 / n as f64; n];
+
+    for _ in 0..max_iter {
+        let dangling: f64 = (0..n).filter(|&v| out_degree[v] == 0).map(|v| rank[v]).sum();
+        let mut next = vec![(1.0 - damping + damping * dangling) / n as f64; n];
+        for edge in graph.raw_edges() {
+            let source = edge.source().index();
+            next[edge.target().index()] += damping * rank[source] / out_degree[source] as f64;
+        }
+
+        let delta: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if delta < tolerance {
+            break;
+        }
+    }
+    rank
+}
+
+/*
+    Betweenness centrality with Brandes' algorithm, following links as stored
+    With 'pivots' set to k below the node count, only k shortest-path trees from
+    pseudo-randomly chosen sources are accumulated and scaled by n / k, which makes
+    the cost O(k * (n + m)) instead of O(n * (n + m)). 'None' is exact.
+*/
+pub fn betweenness(graph: &ProductGraph, pivots: Option<usize>) -> Vec<f64> {
+    let n = graph.node_count();
+    let sources = pivot_nodes(n, pivots);
+    let mut centrality = vec![0.0; n];
+
+    let mut dist = vec![-1i64; n];
+    let mut sigma = vec![0.0f64; n];
+    let mut delta = vec![0.0f64; n];
+    let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut order: Vec<usize> = Vec::with_capacity(n);
+    let mut queue = VecDeque::new();
+
+    for &source in &sources {
+        // Shortest-path counts from the source, in BFS order
+        for v in order.drain(..) {
+            dist[v] = -1;
+            sigma[v] = 0.0;
+            delta[v] = 0.0;
+            preds[v].clear();
+        }
+        dist[source] = 0;
+        sigma[source] = 1.0;
+        queue.push_back(source);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            for w in graph.neighbors(NodeIndex::new(v)) {
+                let w = w.index();
+                if dist[w] < 0 {
+                    dist[w] = dist[v] + 1;
+                    queue.push_back(w);
+                }
+                if dist[w] == dist[v] + 1 {
+                    sigma[w] += sigma[v];
+                    preds[w].push(v);
+                }
+            }
+        }
+
+        // Dependencies flow back from the farthest nodes
+        for &w in order.iter().rev() {
+            for &v in &preds[w] {
+                delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+            }
+            if w != source {
+                centrality[w] += delta[w];
+            }
+        }
+    }
+
+    if !sources.is_empty() && sources.len() < n {
+        let scale = n as f64 / sources.len() as f64;
+        centrality.iter_mut().for_each(|c| *c *= scale);
+    }
+    centrality
+}
+
+/*
+    Closeness centrality over outgoing links, in the Wasserman-Faust form
+    (reached / (n - 1)) * (reached / total distance), so products that only reach a
+    small part of a disconnected graph don't look central. Products reaching nothing
+    score 0. With 'pivots' set, distances to k sampled products stand in for the
+    distances to all of them.
+*/
+pub fn closeness(graph: &ProductGraph, pivots: Option<usize>) -> Vec<f64> {
+    let n = graph.node_count();
+    let mut dist = vec![-1i64; n];
+    let mut queue = VecDeque::new();
+    let score = |reached: usize, total: i64, others: usize| {
+        if total == 0 || others == 0 {
+            0.0
+        } else {
+            (reached as f64 / others as f64) * (reached as f64 / total as f64)
+        }
+    };
+
+    let sources = pivot_nodes(n, pivots);
+    if sources.len() == n {
+        return (0..n)
+            .map(|v| {
+                bfs(graph, v, Direction::Outgoing, &mut dist, &mut queue);
+                let (reached, total) = reach(&dist, v);
+                score(reached, total, n - 1)
+            })
+            .collect();
+    }
+
+    // Walking links backwards from a pivot gives every product's distance to it
+    let mut reached = vec![0usize; n];
+    let mut total = vec![0i64; n];
+    for &pivot in &sources {
+        bfs(graph, pivot, Direction::Incoming, &mut dist, &mut queue);
+        for v in (0..n).filter(|&v| v != pivot && dist[v] > 0) {
+            reached[v] += 1;
+            total[v] += dist[v];
+        }
+    }
+    let is_pivot = {
+        let mut is_pivot = vec![false; n];
+        sources.iter().for_each(|&p| is_pivot[p] = true);
+        is_pivot
+    };
+    (0..n)
+        .map(|v| score(reached[v], total[v], sources.len() - is_pivot[v] as usize))
+        .collect()
+}
+
+// Hop distances from 'source' along edges in 'direction'; -1 for unreachable nodes
+fn bfs(
+    graph: &ProductGraph,
+    source: usize,
+    direction: Direction,
+    dist: &mut [i64],
+    queue: &mut VecDeque<usize>,
+) {
+    dist.fill(-1);
+    dist[source] = 0;
+    queue.push_back(source);
+    while let Some(v) = queue.pop_front() {
+        for w in graph.neighbors_directed(NodeIndex::new(v), direction) {
+            if dist[w.index()] < 0 {
+                dist[w.index()] = dist[v] + 1;
+                queue.push_back(w.index());
+            }
+        }
+    }
+}
+
+// Number of other nodes reached and their total distance
+fn reach(dist: &[i64], source: usize) -> (usize, i64) {
+    dist.iter()
+        .enumerate()
+        .filter(|&(v, &d)| v != source && d > 0)
+        .fold((0, 0), |(reached, total), (_, &d)| (reached + 1, total + d))
+}
+
+// Every node, or 'pivots' of them picked by a seeded partial Fisher-Yates shuffle
+fn pivot_nodes(n: usize, pivots: Option<usize>) -> Vec<usize> {
+    let mut nodes: Vec<usize> = (0..n).collect();
+    let Some(k) = pivots.filter(|&k| k < n) else {
+        return nodes;
+    };
+
+    let mut state = PIVOT_SEED;
+    for i in 0..k {
+        // SplitMix64
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        let j = i + (z % (n - i) as u64) as usize;
+        nodes.swap(i, j);
+    }
+    nodes.truncate(k);
+    nodes
+}
@@ -0,0 +1,207 @@
+// Module for modularity-based community detection (Louvain) over the product graph
+
+use std::collections::HashMap;
+
+use crate::loader::ProductGraph;
+
+// Gains below this are treated as no gain, so rounding noise can't make nodes flip forever
+const MIN_GAIN: f64 = 1e-12;
+
+// A split of the graph's nodes into communities
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition {
+    pub membership: Vec<usize>,         // Community of each node, indexed by 'NodeIndex::index()'
+    pub modularity: f64,                // Modularity of the whole partition
+    pub community_modularity: Vec<f64>, // Each community's share of 'modularity'
+}
+
+impl Partition {
+    pub fn community_count(&self) -> usize {
+        self.community_modularity.len()
+    }
+}
+
+// Undirected weighted graph the Louvain levels work on; a community becomes one node
+// per level, its inner links a self-loop weight
+#[derive(Clone)]
+struct Level {
+    adjacency: Vec<Vec<(usize, f64)>>,
+    self_loops: Vec<f64>, // Sum of A_ii, i.e. every inner link counted from both ends
+}
+
+impl Level {
+    // Each linked pair weighs 1, or 2 when the dump lists the link both ways. Weights come
+    // from 'SimilarLink::reciprocal' rather than edge counts, so they don't depend on how
+    // many edges the graph mode stores per link
+    fn from_graph(graph: &ProductGraph) -> Level {
+        let n = graph.node_count();
+        let mut weights: Vec<HashMap<usize, f64>> = vec![HashMap::new(); n];
+        for edge in graph.raw_edges() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            if a != b {
+                let weight = if edge.weight.reciprocal { 2.0 } else { 1.0 };
+                for (from, to) in [(a, b), (b, a)] {
+                    let entry = weights[from].entry(to).or_default();
+                    *entry = f64::max(*entry, weight);
+                }
+            }
+        }
+        Level { adjacency: sorted(weights), self_loops: vec![0.0; n] }
+    }
+
+    fn len(&self) -> usize {
+        self.self_loops.len()
+    }
+
+    fn degree(&self, node: usize) -> f64 {
+        self.self_loops[node] + self.adjacency[node].iter().map(|(_, w)| w).sum::<f64>()
+    }
+
+    /*
+        Moves single nodes to the neighboring community with the best modularity gain
+        until nothing moves. Nodes are visited in index order and ties keep the first
+        community found, so the result is deterministic. Returns the community of each
+        node, renumbered from 0, and whether any node moved.
+    */
+    fn local_moves(&self, total_weight: f64) -> (Vec<usize>, bool) {
+        let n = self.len();
+        let degrees: Vec<f64> = (0..n).map(|v| self.degree(v)).collect();
+        let mut community: Vec<usize> = (0..n).collect();
+        let mut totals = degrees.clone();
+        let mut weight_to = vec![0.0; n];
+        let mut touched = Vec::new();
+        let mut moved_any = false;
+
+        loop {
+            let mut moved = false;
+            for v in 0..n {
+                let own = community[v];
+                for &(u, w) in &self.adjacency[v] {
+                    let c = community[u];
+                    if weight_to[c] == 0.0 {
+                        touched.push(c);
+                    }
+                    weight_to[c] += w;
+                }
+
+                totals[own] -= degrees[v];
+                let gain = |c: usize| weight_to[c] - totals[c] * degrees[v] / total_weight;
+                let mut best = own;
+                let mut best_gain = gain(own);
+                for &c in &touched {
+                    if gain(c) > best_gain + MIN_GAIN {
+                        best = c;
+                        best_gain = gain(c);
+                    }
+                }
+                totals[best] += degrees[v];
+                community[v] = best;
+                moved |= best != own;
+
+                for c in touched.drain(..) {
+                    weight_to[c] = 0.0;
+                }
+            }
+            if !moved {
+                break;
+            }
+            moved_any = true;
+        }
+
+        (renumber(&community), moved_any)
+    }
+
+    // Collapses every community into a single node of the next level
+    fn aggregate(&self, community: &[usize], count: usize) -> Level {
+        let mut weights: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+        let mut self_loops = vec![0.0; count];
+        for v in 0..self.len() {
+            let c = community[v];
+            self_loops[c] += self.self_loops[v];
+            for &(u, w) in &self.adjacency[v] {
+                if community[u] == c {
+                    self_loops[c] += w;
+                } else {
+                    *weights[c].entry(community[u]).or_default() += w;
+                }
+            }
+        }
+        Level { adjacency: sorted(weights), self_loops }
+    }
+}
+
+/*
+    Louvain community detection
+    Links are read as undirected, a link listed both ways counting twice. Each level
+    moves nodes between communities while modularity improves, then merges every
+    community into one node; this repeats until a level changes nothing.
+*/
+pub fn louvain(graph: &ProductGraph) -> Partition {
+    let base = Level::from_graph(graph);
+    let total_weight: f64 = (0..base.len()).map(|v| base.degree(v)).sum();
+    let mut membership: Vec<usize> = (0..base.len()).collect();
+
+    if total_weight > 0.0 {
+        let mut level = base.clone();
+        loop {
+            let (community, moved) = level.local_moves(total_weight);
+            if !moved {
+                break;
+            }
+            membership.iter_mut().for_each(|m| *m = community[*m]);
+            let count = community.iter().max().map_or(0, |&c| c + 1);
+            level = level.aggregate(&community, count);
+        }
+    }
+
+    let count = membership.iter().max().map_or(0, |&c| c + 1);
+    let community_modularity = modularity_by_community(&base, &membership, count, total_weight);
+    Partition {
+        modularity: community_modularity.iter().sum(),
+        membership,
+        community_modularity,
+    }
+}
+
+// Q_c = (inner weight / 2m) - (total degree / 2m)^2 for every community c
+fn modularity_by_community(level: &Level, membership: &[usize], count: usize, total_weight: f64) -> Vec<f64> {
+    if total_weight == 0.0 {
+        return vec![0.0; count];
+    }
+    let mut inner = vec![0.0; count];
+    let mut totals = vec![0.0; count];
+    for v in 0..level.len() {
+        let c = membership[v];
+        totals[c] += level.degree(v);
+        inner[c] += level.self_loops[v];
+        inner[c] += level.adjacency[v].iter()
+            .filter(|(u, _)| membership[*u] == c)
+            .map(|(_, w)| w)
+            .sum::<f64>();
+    }
+    (0..count)
+        .map(|c| inner[c] / total_weight - (totals[c] / total_weight).powi(2))
+        .collect()
+}
+
+// Adjacency lists in neighbor order, so iteration doesn't depend on hashing
+fn sorted(weights: Vec<HashMap<usize, f64>>) -> Vec<Vec<(usize, f64)>> {
+    weights.into_iter()
+        .map(|neighbors| {
+            let mut neighbors: Vec<_> = neighbors.into_iter().collect();
+            neighbors.sort_unstable_by_key(|&(u, _)| u);
+            neighbors
+        })
+        .collect()
+}
+
+// Community ids renumbered 0.. in order of first appearance
+fn renumber(community: &[usize]) -> Vec<usize> {
+    let mut ids = HashMap::new();
+    community.iter()
+        .map(|c| {
+            let next = ids.len();
+            *ids.entry(*c).or_insert(next)
+        })
+        .collect()
+}
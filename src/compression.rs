@@ -0,0 +1,96 @@
+// Module for reading compressed dumps, detected by their magic bytes
+
+use flate2::bufread::MultiGzDecoder;
+use std::io::{self, BufRead, BufReader, Read};
+
+// Compression formats recognised from the first bytes of a dump
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Bzip2,
+    Zstd,
+    Xz,
+}
+
+impl Compression {
+    // Identifies the format from the start of a file (needs up to 6 bytes)
+    pub fn detect(header: &[u8]) -> Compression {
+        if header.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if header.starts_with(b"BZh") {
+            Compression::Bzip2
+        } else if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else if header.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Compression::Xz
+        } else {
+            Compression::None
+        }
+    }
+}
+
+// A dump that is decompressed on the fly while it is read
+pub enum DumpReader<R> {
+    Plain(R),
+    Gzip(BufReader<MultiGzDecoder<R>>),
+}
+
+/*
+    Wraps a reader so plain and compressed dumps read the same way
+    Only gzip (what SNAP ships) is decompressed; bzip2, zstd and xz are recognised
+    and rejected with an 'Unsupported' error instead of being parsed as text.
+*/
+pub fn open_dump<R: BufRead>(mut reader: R) -> io::Result<DumpReader<R>> {
+    let compression = Compression::detect(reader.fill_buf()?);
+    match compression {
+        Compression::None => Ok(DumpReader::Plain(reader)),
+        Compression::Gzip => Ok(DumpReader::Gzip(BufReader::new(MultiGzDecoder::new(reader)))),
+        other => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{:?} compressed dumps are not supported, decompress it or use gzip", other),
+        )),
+    }
+}
+
+impl<R> DumpReader<R> {
+    pub fn compression(&self) -> Compression {
+        match self {
+            DumpReader::Plain(_) => Compression::None,
+            DumpReader::Gzip(_) => Compression::Gzip,
+        }
+    }
+
+    // Gives back the raw (still compressed) reader
+    pub fn into_inner(self) -> R {
+        match self {
+            DumpReader::Plain(reader) => reader,
+            DumpReader::Gzip(decoder) => decoder.into_inner().into_inner(),
+        }
+    }
+}
+
+impl<R: BufRead> Read for DumpReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            DumpReader::Plain(reader) => reader.read(buf),
+            DumpReader::Gzip(decoder) => decoder.read(buf),
+        }
+    }
+}
+
+impl<R: BufRead> BufRead for DumpReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self {
+            DumpReader::Plain(reader) => reader.fill_buf(),
+            DumpReader::Gzip(decoder) => decoder.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match self {
+            DumpReader::Plain(reader) => reader.consume(amt),
+            DumpReader::Gzip(decoder) => decoder.consume(amt),
+        }
+    }
+}
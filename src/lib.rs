@@ -0,0 +1,12 @@
+pub mod loader;
+pub mod analyzer;
+pub mod taxonomy;
+pub mod reviews;
+pub mod compression;
+pub mod output;
+pub mod snapshot;
+pub mod centrality;
+pub mod community;
+pub mod stats;
+pub mod paths;
+mod parallel;
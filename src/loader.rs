@@ -0,0 +1,858 @@
+use petgraph::graph::{Edge, Graph, NodeIndex};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Instant;
+
+use crate::compression::{open_dump, Compression};
+use crate::parallel::parse_parallel;
+use crate::snapshot::{HashingReader, SourceStamp};
+use crate::reviews::{parse_review, parse_review_summary, Review, ReviewSummary};
+use crate::taxonomy::{parse_category_path, Category, CategoryTree};
+
+// Whether a record is still listed or is a bare 'discontinued product' stub
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProductStatus {
+    #[default]
+    Active,
+    Discontinued,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Product {
+    pub id: Option<u32>,
+    pub asin: String,
+    pub title: String,
+    pub group: String,
+    pub salesrank: i32,
+    pub similar: Vec<String>,
+    pub categories: Vec<Vec<Category>>,
+    pub review_summary: ReviewSummary,
+    pub reviews: Vec<Review>,
+    pub status: ProductStatus,
+}
+
+// Where a link between two products comes from; the SNAP metadata only has 'similar:'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    #[default]
+    Similar,
+}
+
+// Payload of a graph edge, pointing from a product to one it lists as similar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct SimilarLink {
+    pub rank: u32,         // 1-based position of the target in the source's similar list
+    pub reciprocal: bool,  // Whether the target lists the source as well (always so for self-loops)
+    pub kind: LinkKind,
+}
+
+impl SimilarLink {
+    // Link strength for weighted measures: 1 for the first similar product, 1/2 for the second...
+    pub fn weight(&self) -> f32 {
+        1.0 / self.rank.max(1) as f32
+    }
+}
+
+// Product graph: one node per ASIN, one edge per similar-product link
+pub type ProductGraph = Graph<String, SimilarLink>;
+
+#[derive(Default)]
+pub struct ProductDataset {
+    pub graph: ProductGraph,
+    pub graph_mode: GraphMode, // How 'graph' was built from the similar lists
+    pub unresolved: UnresolvedNodes, // Whether placeholder nodes were kept
+    pub parse_mode: ParseMode, // Whether the parse stopped at anomalies or noted them
+    pub products: HashMap<String, Product>,
+    pub categories: CategoryTree,
+    pub ids: IdIndex,
+    pub discontinued: usize,
+    pub source_hash: Option<u64>, // Content hash of the dump this was parsed from
+    pub source_stamp: Option<SourceStamp>, // Size and mtime of that dump, checked before the hash
+}
+
+impl ProductDataset {
+    // Looks a product up by the numeric SNAP 'Id:' instead of its ASIN
+    pub fn product_by_id(&self, id: u32) -> Option<&Product> {
+        self.ids.asin(id).and_then(|asin| self.products.get(asin))
+    }
+
+    // Whether a graph node is a placeholder for an ASIN with no record in the dump
+    pub fn is_unresolved(&self, node: NodeIndex) -> bool {
+        !self.products.contains_key(&self.graph[node])
+    }
+
+    // All placeholder nodes, in node order
+    pub fn unresolved_nodes(&self) -> Vec<NodeIndex> {
+        self.graph.node_indices().filter(|node| self.is_unresolved(*node)).collect()
+    }
+}
+
+// Two-way mapping between SNAP numeric ids and ASINs, used to join with the
+// id-keyed SNAP edge lists such as com-amazon.ungraph.txt
+#[derive(Debug, Clone, Default)]
+pub struct IdIndex {
+    asin_by_id: HashMap<u32, String>,
+    id_by_asin: HashMap<String, u32>,
+}
+
+impl IdIndex {
+    // Maps 'id' and 'asin' to each other, dropping whatever either was mapped to before
+    pub fn insert(&mut self, id: u32, asin: &str) {
+        if let Some(old_asin) = self.asin_by_id.insert(id, asin.to_string()) {
+            if old_asin != asin {
+                self.id_by_asin.remove(&old_asin);
+            }
+        }
+        if let Some(old_id) = self.id_by_asin.insert(asin.to_string(), id) {
+            if old_id != id {
+                self.asin_by_id.remove(&old_id);
+            }
+        }
+    }
+
+    pub fn asin(&self, id: u32) -> Option<&str> {
+        self.asin_by_id.get(&id).map(String::as_str)
+    }
+
+    pub fn id(&self, asin: &str) -> Option<u32> {
+        self.id_by_asin.get(asin).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.asin_by_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.asin_by_id.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.asin_by_id.iter().map(|(id, asin)| (*id, asin.as_str()))
+    }
+}
+
+// Everything that can go wrong while loading the dump. Each variant carries the 1-based
+// line number and the raw text of the line where parsing stopped, so a failure deep in
+// the full file can be found again. 'line' is empty when there was no line to report.
+#[derive(Debug)]
+pub enum LoaderError {
+    Io { line_number: usize, line: String, source: io::Error },
+    MalformedField { line_number: usize, line: String, field: &'static str },
+    BadInteger { line_number: usize, line: String, field: &'static str },
+    TruncatedRecord { line_number: usize, line: String },
+    EmptyDataset { line_number: usize, line: String },
+}
+
+impl LoaderError {
+    pub fn line_number(&self) -> usize {
+        match self {
+            LoaderError::Io { line_number, .. }
+            | LoaderError::MalformedField { line_number, .. }
+            | LoaderError::BadInteger { line_number, .. }
+            | LoaderError::TruncatedRecord { line_number, .. }
+            | LoaderError::EmptyDataset { line_number, .. } => *line_number,
+        }
+    }
+
+    pub fn line(&self) -> &str {
+        match self {
+            LoaderError::Io { line, .. }
+            | LoaderError::MalformedField { line, .. }
+            | LoaderError::BadInteger { line, .. }
+            | LoaderError::TruncatedRecord { line, .. }
+            | LoaderError::EmptyDataset { line, .. } => line,
+        }
+    }
+}
+
+impl fmt::Display for LoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoaderError::Io { line_number, source, .. } => {
+                write!(f, "I/O error at line {}: {}", line_number, source)
+            }
+            LoaderError::MalformedField { line_number, line, field } => {
+                write!(f, "Malformed '{}' field at line {}: {:?}", field, line_number, line)
+            }
+            LoaderError::BadInteger { line_number, line, field } => {
+                write!(f, "Bad integer in '{}' field at line {}: {:?}", field, line_number, line)
+            }
+            LoaderError::TruncatedRecord { line_number, line } => {
+                write!(f, "Record starting at line {} ends before its ASIN: {:?}", line_number, line)
+            }
+            LoaderError::EmptyDataset { line_number, .. } => {
+                write!(f, "Dataset appears to be empty - no valid products found in {} lines", line_number)
+            }
+        }
+    }
+}
+
+impl Error for LoaderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoaderError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// How the parser reacts to lines it can't make sense of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    // Fail with a 'LoaderError' on the first anomaly
+    Strict,
+    // Skip or default the offending line and note it in the 'LoadReport'
+    #[default]
+    Lenient,
+}
+
+/*
+    How similar-product links become graph edges
+    Directed    - one edge per listed link, from the listing product to the listed one
+    Undirected  - every link is stored in both directions, so neighbors are the same
+                  either way round and strongly connected components are plain
+                  connected components
+    Mutual      - only links both products list are kept (in both directions)
+    The graph type stays directed; 'SimilarLink::reciprocal' still tells which links
+    the dump listed both ways.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphMode {
+    #[default]
+    Directed,
+    Undirected,
+    Mutual,
+}
+
+impl GraphMode {
+    // Number of links the graph stands for; undirected and mutual links are two edges each,
+    // except self-loops, which are never mirrored
+    pub fn link_count(self, graph: &ProductGraph) -> usize {
+        self.count_links(graph.raw_edges())
+    }
+
+    // Like 'link_count', for a subset of a graph's edges that keeps mirrored pairs together
+    pub fn count_links<'g>(self, edges: impl IntoIterator<Item = &'g Edge<SimilarLink>>) -> usize {
+        let (mut edge_count, mut loops) = (0, 0);
+        for edge in edges {
+            edge_count += 1;
+            loops += usize::from(edge.source() == edge.target());
+        }
+        match self {
+            GraphMode::Directed => edge_count,
+            GraphMode::Undirected | GraphMode::Mutual => (edge_count - loops) / 2 + loops,
+        }
+    }
+
+    // Turns a graph built from the similar lists as listed into this interpretation
+    fn apply(self, graph: &mut ProductGraph) {
+        match self {
+            GraphMode::Directed => {}
+            GraphMode::Undirected => {
+                let mirrors: Vec<_> = graph.raw_edges().iter()
+                    .filter(|e| !e.weight.reciprocal)
+                    .map(|e| (e.target(), e.source(), e.weight))
+                    .collect();
+                for (source, target, link) in mirrors {
+                    graph.add_edge(source, target, link);
+                }
+            }
+            GraphMode::Mutual => graph.retain_edges(|g, e| g[e].reciprocal),
+        }
+    }
+}
+
+impl FromStr for GraphMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "directed" => Ok(GraphMode::Directed),
+            "undirected" => Ok(GraphMode::Undirected),
+            "mutual" => Ok(GraphMode::Mutual),
+            other => Err(format!("Unknown graph mode: {}", other)),
+        }
+    }
+}
+
+// What happens to graph nodes for similar ASINs that never appear as a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnresolvedNodes {
+    // Keep them as placeholder nodes, see 'ProductDataset::is_unresolved'
+    #[default]
+    Keep,
+    // Drop them and every link pointing to them
+    Prune,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoadOptions {
+    pub mode: ParseMode,
+    pub graph_mode: GraphMode,
+    pub unresolved: UnresolvedNodes,
+    // Keep progress messages off stdout, e.g. when it carries JSON or CSV
+    pub quiet: bool,
+    // Parser threads; 0 or 1 parses on the calling thread
+    pub threads: usize,
+}
+
+// A line that was skipped, defaulted or flagged while loading
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIssue {
+    pub line_number: usize,
+    pub line: String,
+    pub field: &'static str,
+}
+
+// Data quality audit of a load. Line-level anomalies only show up here in lenient
+// mode (strict mode fails on them instead); dangling similar-ASINs are a property of
+// the whole dump and are reported in both modes.
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    pub skipped_lines: Vec<LineIssue>,
+    pub defaulted_fields: Vec<LineIssue>,
+    pub duplicate_asins: Vec<LineIssue>,
+    pub duplicate_ids: Vec<LineIssue>, // Records reusing an earlier record's Id under a new ASIN
+    pub dangling_similar: Vec<String>,
+    pub outside_links: usize, // Similar-list entries pointing to ASINs outside the dump
+}
+
+// What 'parse_field' made of a line it couldn't take at face value
+enum FieldIssue {
+    Skipped(&'static str),
+    Defaulted(&'static str),
+    // Defaulted because the value wasn't a number
+    BadInteger(&'static str),
+}
+
+// Streams 'Product' records out of a reader one at a time, so the whole dump never
+// has to sit in memory. A record starts at an 'Id:' line (or a bare 'ASIN:' line when
+// the Id is missing) and ends at a blank line, where the next record starts or at EOF.
+pub struct Records<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+    parser: RecordParser,
+    finished: bool,
+}
+
+pub fn records<R: BufRead>(reader: R) -> Records<R> {
+    records_with_options(reader, &LoadOptions::default())
+}
+
+pub fn records_with_options<R: BufRead>(reader: R, options: &LoadOptions) -> Records<R> {
+    Records {
+        reader,
+        line: String::new(),
+        line_number: 0,
+        parser: RecordParser {
+            mode: options.mode,
+            duplicates: Some(DuplicateCheck::default()),
+            ..Default::default()
+        },
+        finished: false,
+    }
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<Product, LoaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => {
+                    self.finished = true;
+                    return self.parser.finish();
+                }
+                Ok(_) => self.line_number += 1,
+                Err(source) => {
+                    self.finished = true;
+                    return Some(Err(LoaderError::Io {
+                        line_number: self.line_number + 1,
+                        line: self.line.clone(),
+                        source,
+                    }));
+                }
+            }
+
+            if let Some(result) = self.parser.feed(self.line_number, self.line.trim()) {
+                self.finished = result.is_err();
+                return Some(result);
+            }
+        }
+    }
+}
+
+// Flags ASINs and Ids that already appeared earlier in the dump. A repeated ASIN is
+// reported as such even when its Id repeats too, since the whole record is dropped.
+#[derive(Default)]
+pub(crate) struct DuplicateCheck {
+    seen: HashSet<String>,
+    seen_ids: HashSet<u32>,
+}
+
+impl DuplicateCheck {
+    pub(crate) fn check(
+        &mut self,
+        product: &Product,
+        line_number: usize,
+        line: &str,
+        mode: ParseMode,
+        report: &mut LoadReport,
+    ) -> Result<(), LoaderError> {
+        let (field, duplicates) = if !self.seen.insert(product.asin.clone()) {
+            ("ASIN", &mut report.duplicate_asins)
+        } else if product.id.is_some_and(|id| !self.seen_ids.insert(id)) {
+            ("Id", &mut report.duplicate_ids)
+        } else {
+            return Ok(());
+        };
+
+        let line = line.to_string();
+        if mode == ParseMode::Strict {
+            return Err(LoaderError::MalformedField { line_number, line, field });
+        }
+        duplicates.push(LineIssue { line_number, line, field });
+        Ok(())
+    }
+}
+
+// Line-at-a-time state machine behind 'Records'. Feeding a line hands back the previous
+// record once a new one begins. Without a 'DuplicateCheck' repeated ASINs are left for
+// the caller to catch, which the parallel loader does across chunks.
+#[derive(Default)]
+pub(crate) struct RecordParser {
+    pub(crate) mode: ParseMode,
+    current: Option<Product>,
+    start_line_number: usize,
+    start_line: String,
+    // Where the record handed back last started
+    emitted_line_number: usize,
+    emitted_line: String,
+    // The record's 'categories: N' line, checked against its path lines once it ends
+    categories: Option<DeclaredCount>,
+    pub(crate) duplicates: Option<DuplicateCheck>,
+    pub(crate) report: LoadReport,
+}
+
+// A count a record declares up front, and how many lines actually followed
+struct DeclaredCount {
+    declared: usize,
+    seen: usize,
+    line_number: usize,
+    line: String,
+}
+
+impl RecordParser {
+    pub(crate) fn feed(&mut self, line_number: usize, line: &str) -> Option<Result<Product, LoaderError>> {
+        if let Some(id) = line.strip_prefix("Id:") {
+            // A bad Id still opens a record, just without an Id
+            let id = id.trim().parse().ok();
+            if id.is_none() {
+                if let Err(err) = self.note(FieldIssue::BadInteger("Id"), line_number, line) {
+                    return Some(Err(err));
+                }
+            }
+            let next = Product {
+                id,
+                salesrank: -1,
+                ..Default::default()
+            };
+            return self.start(line_number, line, next);
+        }
+
+        if let Some(asin) = line.strip_prefix("ASIN:") {
+            let asin = asin.trim();
+            if asin.is_empty() {
+                return self.note(FieldIssue::Skipped("ASIN"), line_number, line).err().map(Err);
+            }
+            // The ASIN normally belongs to the record its 'Id:' line just opened
+            if let Some(product) = self.current.as_mut().filter(|p| p.asin.is_empty()) {
+                product.asin = asin.to_string();
+                return None;
+            }
+            let next = Product {
+                asin: asin.to_string(),
+                salesrank: -1,
+                ..Default::default()
+            };
+            return self.start(line_number, line, next);
+        }
+
+        // A blank line closes the record in progress
+        if line.is_empty() && self.current.is_some() {
+            return self.finish();
+        }
+
+        if self.current.is_some() {
+            if let Some(count) = line.strip_prefix("categories:") {
+                let Ok(declared) = count.trim().parse() else {
+                    return self.note(FieldIssue::BadInteger("categories"), line_number, line).err().map(Err);
+                };
+                let line = line.to_string();
+                self.categories = Some(DeclaredCount { declared, seen: 0, line_number, line });
+                return None;
+            }
+            if let (true, Some(count)) = (line.starts_with('|'), self.categories.as_mut()) {
+                count.seen += 1;
+            }
+        }
+
+        let issue = match self.current.as_mut() {
+            Some(product) => parse_field(line, product),
+            // Only the dump's header lines belong outside a record
+            None if line.is_empty() || line.starts_with('#') || line.starts_with("Total items:") => None,
+            None => Some(FieldIssue::Skipped("unknown")),
+        };
+        match issue {
+            Some(issue) => self.note(issue, line_number, line).err().map(Err),
+            None => None,
+        }
+    }
+
+    // Fails in strict mode, otherwise records the anomaly in the report
+    fn note(&mut self, issue: FieldIssue, line_number: usize, line: &str) -> Result<(), LoaderError> {
+        let line = line.to_string();
+        if self.mode == ParseMode::Strict {
+            return Err(match issue {
+                FieldIssue::BadInteger(field) => LoaderError::BadInteger { line_number, line, field },
+                FieldIssue::Skipped(field) | FieldIssue::Defaulted(field) => {
+                    LoaderError::MalformedField { line_number, line, field }
+                }
+            });
+        }
+
+        match issue {
+            FieldIssue::Skipped(field) => {
+                self.report.skipped_lines.push(LineIssue { line_number, line, field });
+            }
+            FieldIssue::Defaulted(field) | FieldIssue::BadInteger(field) => {
+                self.report.defaulted_fields.push(LineIssue { line_number, line, field });
+            }
+        }
+        Ok(())
+    }
+
+    fn start(&mut self, line_number: usize, line: &str, next: Product) -> Option<Result<Product, LoaderError>> {
+        let done = self.finish();
+        self.current = Some(next);
+        self.start_line_number = line_number;
+        self.start_line = line.to_string();
+        done
+    }
+
+    // Hands back the record in progress, which must have picked up an ASIN by now.
+    // Lenient mode drops a record without one and notes its first line as skipped.
+    pub(crate) fn finish(&mut self) -> Option<Result<Product, LoaderError>> {
+        let product = self.current.take()?;
+        let categories = self.categories.take();
+        if product.asin.is_empty() {
+            let line_number = self.start_line_number;
+            let line = std::mem::take(&mut self.start_line);
+            if self.mode == ParseMode::Strict {
+                return Some(Err(LoaderError::TruncatedRecord { line_number, line }));
+            }
+            self.report.skipped_lines.push(LineIssue { line_number, line, field: "ASIN" });
+            return None;
+        }
+
+        if let Some(count) = categories.filter(|count| count.seen != count.declared) {
+            if let Err(err) = self.note(FieldIssue::Defaulted("categories"), count.line_number, &count.line) {
+                return Some(Err(err));
+            }
+        }
+
+        self.emitted_line_number = self.start_line_number;
+        self.emitted_line = std::mem::take(&mut self.start_line);
+        if let Some(duplicates) = self.duplicates.as_mut() {
+            let checked = duplicates.check(
+                &product,
+                self.emitted_line_number,
+                &self.emitted_line,
+                self.mode,
+                &mut self.report,
+            );
+            if let Err(err) = checked {
+                return Some(Err(err));
+            }
+        }
+        Some(Ok(product))
+    }
+
+    // Line number and text of the line that started the record handed back last
+    pub(crate) fn take_emitted(&mut self) -> (usize, String) {
+        (self.emitted_line_number, std::mem::take(&mut self.emitted_line))
+    }
+}
+
+impl<R> Records<R> {
+    // Number of lines consumed from the reader so far
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    // Anomalies noted so far (always empty in strict mode)
+    pub fn report(&self) -> &LoadReport {
+        &self.parser.report
+    }
+
+    pub fn into_report(self) -> LoadReport {
+        self.parser.report
+    }
+
+    // Gives back the underlying reader along with the report
+    pub fn into_inner(self) -> (R, LoadReport) {
+        (self.reader, self.parser.report)
+    }
+}
+
+// Fills in a single attribute line of the record currently being parsed. Returns what
+// went wrong when the line had to be skipped or a field fell back to its default.
+fn parse_field(line: &str, product: &mut Product) -> Option<FieldIssue> {
+    if line.is_empty() {
+        return None;
+    }
+
+    if let Some(title) = line.strip_prefix("title:") {
+        product.title = title.trim().to_string();
+    } else if let Some(group) = line.strip_prefix("group:") {
+        product.group = group.trim().to_string();
+    } else if let Some(rank) = line.strip_prefix("salesrank:") {
+        let Ok(rank) = rank.trim().parse() else {
+            product.salesrank = -1;
+            return Some(FieldIssue::BadInteger("salesrank"));
+        };
+        product.salesrank = rank;
+    } else if let Some(similar) = line.strip_prefix("similar:") {
+        // First token is the count, the rest are the similar ASINs, which are kept as
+        // listed even when the count disagrees
+        let mut tokens = similar.split_whitespace();
+        let declared = tokens.next().map(str::parse::<usize>);
+        product.similar = tokens.map(str::to_string).collect();
+        match declared {
+            Some(Ok(count)) if count == product.similar.len() => {}
+            Some(Ok(_)) => return Some(FieldIssue::Defaulted("similar")),
+            _ => return Some(FieldIssue::BadInteger("similar")),
+        }
+    } else if line == "discontinued product" {
+        product.status = ProductStatus::Discontinued;
+    } else if line.starts_with('|') {
+        // Category paths follow the 'categories: N' line, one path per line
+        match parse_category_path(line) {
+            Some(path) => product.categories.push(path),
+            None => return Some(FieldIssue::Skipped("categories")),
+        }
+    } else if let Some(summary) = line.strip_prefix("reviews:") {
+        let Some(summary) = parse_review_summary(summary) else {
+            product.review_summary = ReviewSummary::default();
+            return Some(FieldIssue::Defaulted("reviews"));
+        };
+        product.review_summary = summary;
+    } else if line.starts_with(|c: char| c.is_ascii_digit()) {
+        // Individual reviews follow the summary line and start with their date
+        match parse_review(line) {
+            Some(review) => product.reviews.push(review),
+            None => return Some(FieldIssue::Skipped("review")),
+        }
+    } else {
+        return Some(FieldIssue::Skipped("unknown"));
+    }
+    None
+}
+
+// Checking for the amazon data file and returns a 'ProdcutDataset' or a 'LoaderError'
+pub fn load_dataset(file_path: &str) -> Result<ProductDataset, LoaderError> {
+    load_dataset_with_options(file_path, &LoadOptions::default()).map(|(dataset, _)| dataset)
+}
+
+// Same as 'load_dataset' but with a choice of parse mode, also returning the audit report
+pub fn load_dataset_with_options(
+    file_path: &str,
+    options: &LoadOptions,
+) -> Result<(ProductDataset, LoadReport), LoaderError> {
+    //adding the timer to check how long the import took
+    let timer = Instant::now();
+
+    let path = Path::new(file_path);
+    let open_error = |source| LoaderError::Io { line_number: 0, line: String::new(), source };
+    if !path.exists() {
+        let message = format!("Dataset file not found: {}", file_path);
+        return Err(open_error(io::Error::new(io::ErrorKind::NotFound, message)));
+    }
+
+    if !options.quiet {
+        println!("Loading dataset from: {}", file_path);
+    }
+    // Hash the raw bytes on the way through so snapshots can spot a changed source,
+    // then decompress gzip dumps while streaming
+    let file = File::open(path).map_err(open_error)?;
+    let source_stamp = file.metadata().ok().as_ref().and_then(SourceStamp::from_metadata);
+    let raw = BufReader::new(HashingReader::new(file));
+    let mut reader = open_dump(raw).map_err(open_error)?;
+    if !options.quiet && reader.compression() != Compression::None {
+        println!("Decompressing {:?} stream", reader.compression());
+    }
+
+    let mut builder = DatasetBuilder::default();
+    let (line_count, mut report) = if options.threads > 1 {
+        if !options.quiet {
+            println!("Parsing with {} threads", options.threads);
+        }
+        parse_parallel(&mut reader, options, &mut builder)?
+    } else {
+        let mut stream = records_with_options(&mut reader, options);
+        for record in stream.by_ref() {
+            builder.add(record?);
+        }
+        (stream.line_number(), stream.into_report())
+    };
+
+    // Checking if the dataset is empty
+    if builder.products.is_empty() {
+        return Err(LoaderError::EmptyDataset { line_number: line_count, line: String::new() });
+    }
+
+    let source_hash = Some(reader.into_inner().get_ref().hash());
+    let dataset = builder.build(options, source_hash, source_stamp);
+    let outside: Vec<&String> = dataset.products.values()
+        .flat_map(|product| &product.similar)
+        .filter(|asin| !dataset.products.contains_key(*asin))
+        .collect();
+    report.outside_links = outside.len();
+    report.dangling_similar = outside.into_iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    if !options.quiet {
+        println!("Dataset loaded in {:.2} seconds", timer.elapsed().as_secs_f32());
+        println!("Products processed: {}", dataset.products.len());
+        println!("Discontinued products: {}", dataset.discontinued);
+        println!("Connections established: {}", dataset.graph_mode.link_count(&dataset.graph));
+        println!("Categories indexed: {}", dataset.categories.len());
+        if options.mode == ParseMode::Lenient {
+            println!("Lines skipped: {}", report.skipped_lines.len());
+            println!("Fields defaulted: {}", report.defaulted_fields.len());
+            println!("Duplicate ASINs: {}", report.duplicate_asins.len());
+            println!("Duplicate Ids: {}", report.duplicate_ids.len());
+        }
+        println!("Dangling similar ASINs: {}", report.dangling_similar.len());
+        println!("Links pointing outside the dump: {}", report.outside_links);
+        if options.unresolved == UnresolvedNodes::Prune {
+            println!("Placeholder nodes pruned");
+        }
+    }
+
+    Ok((dataset, report))
+}
+
+/*
+    Collects finished records into a dataset in the order they are handed over
+    Both the sequential and the parallel loader feed records in file order, so the
+    node and edge order of the graph doesn't depend on how the dump was parsed.
+*/
+#[derive(Default)]
+pub(crate) struct DatasetBuilder {
+    graph: ProductGraph,
+    products: HashMap<String, Product>,
+    node_indices: HashMap<String, NodeIndex>,
+    categories: CategoryTree,
+    ids: IdIndex,
+    discontinued: usize,
+}
+
+impl DatasetBuilder {
+    pub(crate) fn add(&mut self, product: Product) {
+        // Only the first record of a repeated ASIN is kept, links and all
+        if self.products.contains_key(&product.asin) {
+            return;
+        }
+        // Every kept record gets a node, linked to the ASINs in its similar list if any
+        process_similar_products(&product, &mut self.graph, &mut self.node_indices);
+        self.categories.insert_product(&product);
+        if product.status == ProductStatus::Discontinued {
+            self.discontinued += 1;
+        }
+        // A repeated Id stays with the record that used it first
+        if let Some(id) = product.id.filter(|id| self.ids.asin(*id).is_none()) {
+            self.ids.insert(id, &product.asin);
+        }
+        self.products.insert(product.asin.clone(), product);
+    }
+
+    fn build(
+        mut self,
+        options: &LoadOptions,
+        source_hash: Option<u64>,
+        source_stamp: Option<SourceStamp>,
+    ) -> ProductDataset {
+        if options.unresolved == UnresolvedNodes::Prune {
+            // 'filter_map' keeps the remaining nodes and edges in their original order
+            let products = &self.products;
+            self.graph = self.graph.filter_map(
+                |_, asin| products.contains_key(asin).then(|| asin.clone()),
+                |_, link| Some(*link),
+            );
+        }
+        options.graph_mode.apply(&mut self.graph);
+        ProductDataset {
+            graph: self.graph,
+            graph_mode: options.graph_mode,
+            unresolved: options.unresolved,
+            parse_mode: options.mode,
+            products: self.products,
+            categories: self.categories,
+            ids: self.ids,
+            discontinued: self.discontinued,
+            source_hash,
+            source_stamp,
+        }
+    }
+}
+
+fn process_similar_products(
+    product: &Product,
+    graph: &mut ProductGraph,
+    node_indices: &mut HashMap<String, NodeIndex>,
+) {
+    let main_node = *node_indices
+        .entry(product.asin.clone())
+        .or_insert_with(|| graph.add_node(product.asin.clone()));
+
+    for (position, similar_asin) in product.similar.iter().enumerate() {
+        let similar_node = *node_indices
+            .entry(similar_asin.clone())
+            .or_insert_with(|| graph.add_node(similar_asin.clone()));
+
+        if graph.contains_edge(main_node, similar_node) {
+            continue;
+        }
+        // Links are marked reciprocal once the second direction turns up; a product
+        // listing itself is its own second direction, so mutual graphs keep self-loops
+        let reverse = graph.find_edge(similar_node, main_node);
+        if let Some(reverse) = reverse {
+            graph[reverse].reciprocal = true;
+        }
+        graph.add_edge(main_node, similar_node, SimilarLink {
+            rank: position as u32 + 1,
+            reciprocal: reverse.is_some() || similar_node == main_node,
+            kind: LinkKind::Similar,
+        });
+    }
+}
@@ -0,0 +1,376 @@
+// Module for exporting analysis results as JSON, JSON Lines or CSV
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::analyzer::{GiantComponent, ProductAnalyzer, TrendCommunity};
+use crate::loader::{GraphMode, Product, ProductDataset, ProductStatus};
+use crate::stats::GraphStats;
+
+// Machine-readable formats every result type can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    JsonLines,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(OutputFormat::Json),
+            "jsonl" | "ndjson" => Ok(OutputFormat::JsonLines),
+            "csv" => Ok(OutputFormat::Csv),
+            other => Err(format!("Unknown output format: {}", other)),
+        }
+    }
+}
+
+/*
+    Row schemas. Every result is exported as a flat list of rows so the same fields
+    come out in all three formats; field names and order are part of the schema.
+*/
+
+// One row of 'top_products_by_connections'
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankedProduct {
+    pub rank: usize,
+    pub asin: String,
+    pub id: Option<u32>,
+    pub title: String,
+    pub group: String,
+    pub salesrank: i32,
+}
+
+// One row of a ranking by score: PageRank, betweenness, closeness or link strength
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScoredProduct {
+    pub rank: usize,
+    pub asin: String,
+    pub id: Option<u32>,
+    pub title: String,
+    pub group: String,
+    pub salesrank: i32,
+    pub score: f64,
+}
+
+// One product of a trend cluster from 'detect_trend_clusters'
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClusterMember {
+    pub cluster: usize,
+    pub cluster_size: usize,
+    pub asin: String,
+    pub title: String,
+    pub group: String,
+    pub salesrank: i32,
+}
+
+// One product of a community from 'detect_communities'
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommunityMember {
+    pub community: usize,
+    pub community_size: usize,
+    pub community_modularity: f64,
+    pub dominant_group: Option<String>,
+    pub asin: String,
+    pub title: String,
+    pub group: String,
+    pub salesrank: i32,
+}
+
+// One product along a chain from 'k_shortest_paths'
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PathStep {
+    pub path: usize,
+    pub step: usize, // 0 for the starting product
+    pub asin: String,
+    pub title: String,
+    pub group: String,
+    pub salesrank: i32,
+}
+
+// One row of 'find_low_competition_products'
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Opportunity {
+    pub rank: usize,
+    pub asin: String,
+    pub title: String,
+    pub group: String,
+    pub salesrank: i32,
+    pub score: f32,
+}
+
+// Flat view of a single product, for formats that can't hold its nested lists
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProductSummary {
+    pub asin: String,
+    pub id: Option<u32>,
+    pub title: String,
+    pub group: String,
+    pub salesrank: i32,
+    pub status: ProductStatus,
+    pub similar_count: usize,
+    pub category_count: usize,
+    pub review_total: u32,
+    pub review_downloaded: u32,
+    pub avg_rating: f32,
+}
+
+// Headline numbers about a loaded dataset
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasetSummary {
+    pub graph_mode: GraphMode,
+    pub products: usize,
+    pub nodes: usize,
+    pub unresolved_nodes: usize,
+    pub connections: usize,
+    pub avg_connections: f32,
+    pub discontinued: usize,
+    pub categories: usize,
+}
+
+// Everything the stats command reports, for the formats that can hold nested values
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasetStats<'a> {
+    pub summary: DatasetSummary,
+    pub graph: GraphStats,
+    pub components: usize,                          // Weakly connected components
+    pub component_sizes: BTreeMap<usize, usize>,    // Component size -> number of components
+    pub giant_component: Option<GiantComponent<'a>>,
+}
+
+// Flat view of 'DatasetStats' for CSV, without the degree and component histograms
+// or the giant component's groups
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsSummary {
+    pub graph_mode: GraphMode,
+    pub products: usize,
+    pub nodes: usize,
+    pub unresolved_nodes: usize,
+    pub edges: usize,
+    pub connections: usize,
+    pub avg_connections: f64,
+    pub discontinued: usize,
+    pub categories: usize,
+    pub max_in_degree: usize,
+    pub max_out_degree: usize,
+    pub max_total_degree: usize,
+    pub density: f64,
+    pub reciprocity: f64,
+    pub global_clustering: f64,
+    pub avg_clustering: f64,
+    pub diameter: usize,
+    pub diameter_exact: bool,
+    pub self_loops: usize,
+    pub dangling_nodes: usize,
+    pub components: usize,
+    pub giant_nodes: Option<usize>,
+    pub giant_edges: Option<usize>,
+    pub giant_node_share: Option<f64>,
+    pub giant_edge_share: Option<f64>,
+}
+
+pub fn dataset_stats<'a>(dataset: &ProductDataset, analyzer: &ProductAnalyzer<'a>) -> DatasetStats<'a> {
+    let component_sizes = analyzer.component_size_histogram();
+    DatasetStats {
+        summary: DatasetSummary::from(dataset),
+        graph: analyzer.graph_stats(),
+        components: component_sizes.values().sum(),
+        component_sizes,
+        giant_component: analyzer.giant_component(),
+    }
+}
+
+pub fn ranked_products(products: &[&Product]) -> Vec<RankedProduct> {
+    products.iter()
+        .enumerate()
+        .map(|(i, p)| RankedProduct {
+            rank: i + 1,
+            asin: p.asin.clone(),
+            id: p.id,
+            title: p.title.clone(),
+            group: p.group.clone(),
+            salesrank: p.salesrank,
+        })
+        .collect()
+}
+
+pub fn scored_products<S: Copy + Into<f64>>(scored: &[(&Product, S)]) -> Vec<ScoredProduct> {
+    scored.iter()
+        .enumerate()
+        .map(|(i, (p, score))| ScoredProduct {
+            rank: i + 1,
+            asin: p.asin.clone(),
+            id: p.id,
+            title: p.title.clone(),
+            group: p.group.clone(),
+            salesrank: p.salesrank,
+            score: (*score).into(),
+        })
+        .collect()
+}
+
+pub fn cluster_members(clusters: &[Vec<&Product>]) -> Vec<ClusterMember> {
+    clusters.iter()
+        .enumerate()
+        .flat_map(|(i, cluster)| {
+            cluster.iter().map(move |p| ClusterMember {
+                cluster: i + 1,
+                cluster_size: cluster.len(),
+                asin: p.asin.clone(),
+                title: p.title.clone(),
+                group: p.group.clone(),
+                salesrank: p.salesrank,
+            })
+        })
+        .collect()
+}
+
+pub fn community_members(communities: &[TrendCommunity]) -> Vec<CommunityMember> {
+    communities.iter()
+        .enumerate()
+        .flat_map(|(i, community)| {
+            community.products.iter().map(move |p| CommunityMember {
+                community: i + 1,
+                community_size: community.size,
+                community_modularity: community.modularity,
+                dominant_group: community.dominant_group.map(str::to_string),
+                asin: p.asin.clone(),
+                title: p.title.clone(),
+                group: p.group.clone(),
+                salesrank: p.salesrank,
+            })
+        })
+        .collect()
+}
+
+pub fn path_steps(paths: &[Vec<&Product>]) -> Vec<PathStep> {
+    paths.iter()
+        .enumerate()
+        .flat_map(|(i, path)| {
+            path.iter().enumerate().map(move |(step, p)| PathStep {
+                path: i + 1,
+                step,
+                asin: p.asin.clone(),
+                title: p.title.clone(),
+                group: p.group.clone(),
+                salesrank: p.salesrank,
+            })
+        })
+        .collect()
+}
+
+pub fn opportunities(scored: &[(&Product, f32)]) -> Vec<Opportunity> {
+    scored.iter()
+        .enumerate()
+        .map(|(i, (p, score))| Opportunity {
+            rank: i + 1,
+            asin: p.asin.clone(),
+            title: p.title.clone(),
+            group: p.group.clone(),
+            salesrank: p.salesrank,
+            score: *score,
+        })
+        .collect()
+}
+
+impl From<&Product> for ProductSummary {
+    fn from(p: &Product) -> Self {
+        ProductSummary {
+            asin: p.asin.clone(),
+            id: p.id,
+            title: p.title.clone(),
+            group: p.group.clone(),
+            salesrank: p.salesrank,
+            status: p.status,
+            similar_count: p.similar.len(),
+            category_count: p.categories.len(),
+            review_total: p.review_summary.total,
+            review_downloaded: p.review_summary.downloaded,
+            avg_rating: p.review_summary.avg_rating,
+        }
+    }
+}
+
+impl From<&ProductDataset> for DatasetSummary {
+    fn from(dataset: &ProductDataset) -> Self {
+        let nodes = dataset.graph.node_count();
+        let connections = dataset.graph_mode.link_count(&dataset.graph);
+        DatasetSummary {
+            graph_mode: dataset.graph_mode,
+            products: dataset.products.len(),
+            nodes,
+            unresolved_nodes: dataset.unresolved_nodes().len(),
+            connections,
+            avg_connections: if nodes == 0 { 0.0 } else { dataset.graph.edge_count() as f32 / nodes as f32 },
+            discontinued: dataset.discontinued,
+            categories: dataset.categories.len(),
+        }
+    }
+}
+
+impl From<&DatasetStats<'_>> for StatsSummary {
+    fn from(stats: &DatasetStats) -> Self {
+        let (summary, graph, giant) = (&stats.summary, &stats.graph, stats.giant_component.as_ref());
+        StatsSummary {
+            graph_mode: graph.graph_mode,
+            products: summary.products,
+            nodes: graph.nodes,
+            unresolved_nodes: summary.unresolved_nodes,
+            edges: graph.edges,
+            connections: graph.links,
+            avg_connections: graph.avg_degree,
+            discontinued: summary.discontinued,
+            categories: summary.categories,
+            max_in_degree: graph.max_in_degree,
+            max_out_degree: graph.max_out_degree,
+            max_total_degree: graph.max_total_degree,
+            density: graph.density,
+            reciprocity: graph.reciprocity,
+            global_clustering: graph.global_clustering,
+            avg_clustering: graph.avg_clustering,
+            diameter: graph.diameter,
+            diameter_exact: graph.diameter_exact,
+            self_loops: graph.self_loops,
+            dangling_nodes: graph.dangling_nodes,
+            components: stats.components,
+            giant_nodes: giant.map(|g| g.nodes),
+            giant_edges: giant.map(|g| g.edges),
+            giant_node_share: giant.map(|g| g.node_share),
+            giant_edge_share: giant.map(|g| g.edge_share),
+        }
+    }
+}
+
+/*
+    Writes rows in the chosen format
+    JSON     - a single pretty-printed array
+    JSONL    - one compact object per line
+    CSV      - a header row followed by one line per row (nothing at all for no rows)
+*/
+pub fn write_rows<T: Serialize, W: Write>(rows: &[T], format: OutputFormat, mut writer: W) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, rows)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::JsonLines => {
+            for row in rows {
+                serde_json::to_writer(&mut writer, row)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Csv => {
+            let mut csv = csv::Writer::from_writer(writer);
+            for row in rows {
+                csv.serialize(row)?;
+            }
+            csv.flush()?;
+        }
+    }
+    Ok(())
+}
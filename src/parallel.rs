@@ -0,0 +1,205 @@
+// Module for parsing a metadata dump on several threads at once
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+use crate::loader::{
+    DatasetBuilder, DuplicateCheck, LoadOptions, LoadReport, LoaderError, ParseMode, Product,
+    RecordParser,
+};
+
+// Chunks are cut at the first 'Id:' line once they have grown this large
+const CHUNK_BYTES: usize = 1 << 20;
+
+// A run of whole records, together with the line number of its first line
+struct Chunk {
+    index: usize,
+    first_line: usize,
+    text: String,
+}
+
+// What a worker made of one chunk: its records with the line each one started on,
+// the problems it noted and the error that stopped it, if any
+struct ParsedChunk {
+    records: Vec<(usize, String, Product)>,
+    report: LoadReport,
+    error: Option<LoaderError>,
+}
+
+/*
+    Parses the dump in parallel and feeds the records into 'builder'
+    The calling thread reads lines and cuts them into chunks at record boundaries ('Id:'
+    lines), 'options.threads' workers parse the chunks, and a merge thread feeds the
+    results into 'builder' in chunk order while reading goes on. Repeated ASINs are
+    checked during the merge since they can span chunks, so the dataset, report and
+    first error match a sequential load exactly. Reading stops once a merged chunk
+    fails, so an early error doesn't wait for the rest of the dump.
+    Returns the number of lines read and the load report.
+*/
+pub(crate) fn parse_parallel<R: BufRead>(
+    reader: &mut R,
+    options: &LoadOptions,
+    builder: &mut DatasetBuilder,
+) -> Result<(usize, LoadReport), LoaderError> {
+    let mode = options.mode;
+    let (chunk_tx, chunk_rx) = mpsc::sync_channel::<Chunk>(options.threads * 2);
+    let chunk_rx = Mutex::new(chunk_rx);
+    let (parsed_tx, parsed_rx) = mpsc::channel::<(usize, ParsedChunk)>();
+    // Set by the merge once a chunk fails, so reading can stop early
+    let failed = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        for _ in 0..options.threads {
+            let chunk_rx = &chunk_rx;
+            let parsed_tx = parsed_tx.clone();
+            scope.spawn(move || loop {
+                let next = chunk_rx.lock().unwrap().recv();
+                let Ok(chunk) = next else { break };
+                // The merge stops listening after the first error, so a failed send is fine
+                let _ = parsed_tx.send((chunk.index, parse_chunk(chunk, mode)));
+            });
+        }
+        drop(parsed_tx);
+
+        let merger = {
+            let failed = &failed;
+            scope.spawn(move || {
+                let mut report = LoadReport::default();
+                let mut duplicates = DuplicateCheck::default();
+                let mut pending = BTreeMap::new();
+                let mut next_index = 0;
+                for (index, parsed) in parsed_rx {
+                    pending.insert(index, parsed);
+                    while let Some(parsed) = pending.remove(&next_index) {
+                        if let Err(err) = merge_chunk(parsed, mode, &mut duplicates, &mut report, builder) {
+                            failed.store(true, Ordering::Relaxed);
+                            return Err(err);
+                        }
+                        next_index += 1;
+                    }
+                }
+                Ok(report)
+            })
+        };
+
+        let (line_count, read_error) = split_chunks(reader, |chunk| {
+            if failed.load(Ordering::Relaxed) {
+                return false;
+            }
+            // Workers only stop once the sender is gone, so this can't fail
+            chunk_tx.send(chunk).expect("parser threads exited early");
+            true
+        });
+        drop(chunk_tx);
+
+        let report = merger.join().expect("merge thread panicked")?;
+        match read_error {
+            Some(err) => Err(err),
+            None => Ok((line_count, report)),
+        }
+    })
+}
+
+// Reads the dump and hands it over in chunks; stops at the first read error or once
+// 'send' declines a chunk
+fn split_chunks<R: BufRead>(
+    reader: &mut R,
+    mut send: impl FnMut(Chunk) -> bool,
+) -> (usize, Option<LoaderError>) {
+    let mut line = String::new();
+    let mut text = String::new();
+    let mut line_number = 0;
+    let mut first_line = 1;
+    let mut index = 0;
+    let mut read_error = None;
+
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(source) => {
+                read_error = Some(LoaderError::Io {
+                    line_number: line_number + 1,
+                    line: line.clone(),
+                    source,
+                });
+                break;
+            }
+        }
+        line_number += 1;
+
+        if text.len() >= CHUNK_BYTES && line.trim().starts_with("Id:") {
+            if !send(Chunk { index, first_line, text: mem::take(&mut text) }) {
+                break;
+            }
+            index += 1;
+            first_line = line_number;
+        }
+        text.push_str(&line);
+    }
+
+    if !text.is_empty() {
+        send(Chunk { index, first_line, text });
+    }
+    (line_number, read_error)
+}
+
+// Runs one chunk through its own parser; duplicates are left to the merge
+fn parse_chunk(chunk: Chunk, mode: ParseMode) -> ParsedChunk {
+    let mut parser = RecordParser::default();
+    parser.mode = mode;
+    let mut records = Vec::new();
+    let mut error = None;
+
+    for (offset, line) in chunk.text.lines().enumerate() {
+        match parser.feed(chunk.first_line + offset, line.trim()) {
+            Some(Ok(product)) => {
+                let (line_number, line) = parser.take_emitted();
+                records.push((line_number, line, product));
+            }
+            Some(Err(err)) => {
+                error = Some(err);
+                break;
+            }
+            None => {}
+        }
+    }
+
+    if error.is_none() {
+        match parser.finish() {
+            Some(Ok(product)) => {
+                let (line_number, line) = parser.take_emitted();
+                records.push((line_number, line, product));
+            }
+            Some(Err(err)) => error = Some(err),
+            None => {}
+        }
+    }
+
+    ParsedChunk { records, report: parser.report, error }
+}
+
+fn merge_chunk(
+    parsed: ParsedChunk,
+    mode: ParseMode,
+    duplicates: &mut DuplicateCheck,
+    report: &mut LoadReport,
+    builder: &mut DatasetBuilder,
+) -> Result<(), LoaderError> {
+    for (line_number, line, product) in parsed.records {
+        duplicates.check(&product, line_number, &line, mode, report)?;
+        builder.add(product);
+    }
+    report.skipped_lines.extend(parsed.report.skipped_lines);
+    report.defaulted_fields.extend(parsed.report.defaulted_fields);
+
+    match parsed.error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,123 @@
+// Module for shortest recommendation chains between products
+
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use crate::loader::ProductGraph;
+
+// Why no chain between two products could be given
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    // The ASIN has no product record in the dataset
+    UnknownProduct(String),
+    // Both products exist but no chain of links leads from one to the other
+    NoPath { from: String, to: String },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::UnknownProduct(asin) => write!(f, "No product with ASIN {}", asin),
+            PathError::NoPath { from, to } => write!(f, "No recommendation chain from {} to {}", from, to),
+        }
+    }
+}
+
+impl Error for PathError {}
+
+/*
+    Shortest path by hop count (BFS), never entering 'blocked_nodes' or using
+    'blocked_edges'. Neighbors are visited in node index order, so among equally short
+    paths the result doesn't change between runs.
+*/
+pub fn shortest_path(
+    graph: &ProductGraph,
+    from: NodeIndex,
+    to: NodeIndex,
+    blocked_nodes: &HashSet<NodeIndex>,
+    blocked_edges: &HashSet<(NodeIndex, NodeIndex)>,
+) -> Option<Vec<NodeIndex>> {
+    let mut parent: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    let mut seen = HashSet::from([from]);
+
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            let mut path = vec![to];
+            while let Some(&previous) = parent.get(path.last().unwrap()) {
+                path.push(previous);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let mut next: Vec<NodeIndex> = graph.neighbors(node)
+            .filter(|n| !blocked_nodes.contains(n) && !blocked_edges.contains(&(node, *n)))
+            .collect();
+        next.sort_unstable();
+        for neighbor in next {
+            if seen.insert(neighbor) {
+                parent.insert(neighbor, node);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    None
+}
+
+/*
+    Up to 'k' shortest loopless paths (Yen's algorithm), shortest first, never entering
+    'blocked_nodes'
+    Every further path branches off one of the paths found so far at a "spur" node,
+    with the links those paths take from there blocked. Equally long candidates are
+    taken in node index order.
+*/
+pub fn k_shortest_paths(
+    graph: &ProductGraph,
+    from: NodeIndex,
+    to: NodeIndex,
+    k: usize,
+    blocked_nodes: &HashSet<NodeIndex>,
+) -> Vec<Vec<NodeIndex>> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let Some(first) = shortest_path(graph, from, to, blocked_nodes, &HashSet::new()) else {
+        return Vec::new();
+    };
+
+    let mut found = vec![first];
+    let mut candidates: Vec<Vec<NodeIndex>> = Vec::new();
+    while found.len() < k {
+        let previous = found.last().unwrap().clone();
+        for i in 0..previous.len() - 1 {
+            let spur = previous[i];
+            let root = &previous[..=i];
+
+            let blocked_edges: HashSet<_> = found.iter()
+                .filter(|path| path.len() > i + 1 && &path[..=i] == root)
+                .map(|path| (path[i], path[i + 1]))
+                .collect();
+            let mut blocked = blocked_nodes.clone();
+            blocked.extend(&root[..i]);
+
+            if let Some(spur_path) = shortest_path(graph, spur, to, &blocked, &blocked_edges) {
+                let mut path = root[..i].to_vec();
+                path.extend(spur_path);
+                if !found.contains(&path) && !candidates.contains(&path) {
+                    candidates.push(path);
+                }
+            }
+        }
+
+        let Some(best) = (0..candidates.len()).min_by(|&a, &b| {
+            candidates[a].len().cmp(&candidates[b].len()).then_with(|| candidates[a].cmp(&candidates[b]))
+        }) else {
+            break;
+        };
+        found.push(candidates.swap_remove(best));
+    }
+    found
+}
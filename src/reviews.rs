@@ -0,0 +1,90 @@
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+// Calendar date of a review as written in the dump, e.g. '2000-7-28'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ReviewDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl FromStr for ReviewDate {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('-');
+        let year = parts.next().ok_or(())?.parse().map_err(|_| ())?;
+        let month: u8 = parts.next().ok_or(())?.parse().map_err(|_| ())?;
+        let day: u8 = parts.next().ok_or(())?.parse().map_err(|_| ())?;
+        if parts.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(());
+        }
+        Ok(ReviewDate { year, month, day })
+    }
+}
+
+// Serialized as 'YYYY-MM-DD' so exported dates sort and parse like any ISO date
+impl Serialize for ReviewDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl fmt::Display for ReviewDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// A single customer review line
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct Review {
+    pub date: ReviewDate,
+    pub customer: String,
+    pub rating: u8,
+    pub votes: u32,
+    pub helpful: u32,
+}
+
+// Totals from the 'reviews: total: X downloaded: Y avg rating: Z' header
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct ReviewSummary {
+    pub total: u32,
+    pub downloaded: u32,
+    pub avg_rating: f32,
+}
+
+// Returns the token that follows 'label' in a whitespace separated line
+fn value_after<'a>(tokens: &[&'a str], label: &str) -> Option<&'a str> {
+    tokens.iter()
+        .position(|token| *token == label)
+        .and_then(|i| tokens.get(i + 1).copied())
+}
+
+// Parses the text after 'reviews:' on a review header line
+pub fn parse_review_summary(line: &str) -> Option<ReviewSummary> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    Some(ReviewSummary {
+        total: value_after(&tokens, "total:")?.parse().ok()?,
+        downloaded: value_after(&tokens, "downloaded:")?.parse().ok()?,
+        avg_rating: value_after(&tokens, "rating:")?.parse().ok()?,
+    })
+}
+
+// Parses one '2000-7-28  cutomer: A2JW67OY8U6HHK  rating: 5  votes: 10  helpful: 9' line.
+// The dump spells the label 'cutomer:', the corrected spelling is accepted too.
+pub fn parse_review(line: &str) -> Option<Review> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let customer = value_after(&tokens, "cutomer:")
+        .or_else(|| value_after(&tokens, "customer:"))?;
+
+    Some(Review {
+        date: tokens.first()?.parse().ok()?,
+        customer: customer.to_string(),
+        rating: value_after(&tokens, "rating:")?.parse().ok()?,
+        votes: value_after(&tokens, "votes:")?.parse().ok()?,
+        helpful: value_after(&tokens, "helpful:")?.parse().ok()?,
+    })
+}
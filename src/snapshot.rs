@@ -0,0 +1,631 @@
+// Module for caching a parsed ProductDataset in a compact binary snapshot
+
+use petgraph::graph::{Graph, NodeIndex};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::{File, Metadata};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use crate::loader::{
+    self, GraphMode, IdIndex, LinkKind, LoadOptions, LoaderError, ParseMode, Product, ProductDataset,
+    ProductStatus, SimilarLink, UnresolvedNodes,
+};
+use crate::reviews::{Review, ReviewDate, ReviewSummary};
+use crate::taxonomy::{Category, CategoryNode, CategoryTree};
+
+const MAGIC: &[u8; 8] = b"AMZSNAP\0";
+// Bump whenever the layout below or the meaning of a stored field changes; older
+// snapshots are then rejected
+pub const SNAPSHOT_VERSION: u32 = 8;
+
+/*
+    Snapshot layout (all integers little endian, strings as u32 length + UTF-8 bytes)
+    header      - magic, version, source hash flag + u64, source stamp flag + size, mtime
+                  seconds + nanoseconds, discontinued count, graph mode, unresolved node
+                  handling, parse mode
+    products    - count, then every product sorted by ASIN
+    graph       - node count + ASIN per node, edge count + (source, target, rank, reciprocal, kind)
+    id index    - count + (id, ASIN) pairs
+    categories  - node count + nodes, roots, category paths per product
+*/
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u32),
+    Corrupt(&'static str),
+    // The source file no longer matches the hash recorded in the snapshot
+    Stale { expected: Option<u64>, found: u64 },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(err) => write!(f, "I/O error on snapshot: {}", err),
+            SnapshotError::BadMagic => write!(f, "Not a dataset snapshot"),
+            SnapshotError::UnsupportedVersion(v) => {
+                write!(f, "Snapshot version {} is not supported (expected {})", v, SNAPSHOT_VERSION)
+            }
+            SnapshotError::Corrupt(what) => write!(f, "Corrupt snapshot: {}", what),
+            SnapshotError::Stale { expected, found } => match expected {
+                Some(expected) => write!(f, "Snapshot is stale: source hash {:016x}, snapshot has {:016x}", found, expected),
+                None => write!(f, "Snapshot is stale: it records no source hash"),
+            },
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            SnapshotError::Corrupt("unexpected end of file")
+        } else {
+            SnapshotError::Io(err)
+        }
+    }
+}
+
+/*
+    Content hashing
+    64-bit FNV-1a, chosen because it is stable across Rust versions and platforms
+*/
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+// Reader adapter that hashes everything read through it
+pub struct HashingReader<R> {
+    inner: R,
+    hash: u64,
+}
+
+impl<R> HashingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, hash: FNV_OFFSET }
+    }
+
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hash = fnv1a(self.hash, &buf[..n]);
+        Ok(n)
+    }
+}
+
+// Hash of a file's full contents, as recorded by 'load_dataset'
+pub fn content_hash(path: &Path) -> io::Result<u64> {
+    let mut reader = HashingReader::new(File::open(path)?);
+    io::copy(&mut reader, &mut io::sink())?;
+    Ok(reader.hash())
+}
+
+// Size and modification time of a source file, a cheap stand-in for its content hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceStamp {
+    pub size: u64,
+    pub modified_secs: u64, // Seconds since the Unix epoch
+    pub modified_nanos: u32,
+}
+
+impl SourceStamp {
+    // None when the platform can't report a modification time
+    pub fn from_metadata(metadata: &Metadata) -> Option<SourceStamp> {
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(SourceStamp {
+            size: metadata.len(),
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+        })
+    }
+}
+
+// Everything ahead of the products, enough to decide whether the rest is worth decoding
+struct Header {
+    source_hash: Option<u64>,
+    source_stamp: Option<SourceStamp>,
+    discontinued: usize,
+    graph_mode: GraphMode,
+    unresolved: UnresolvedNodes,
+    parse_mode: ParseMode,
+}
+
+impl ProductDataset {
+    // Writes the dataset, its graph and indices to a snapshot file
+    pub fn save(&self, path: &str) -> Result<(), SnapshotError> {
+        let mut out = Encoder(BufWriter::new(File::create(path)?));
+        out.bytes(MAGIC)?;
+        out.u32(SNAPSHOT_VERSION)?;
+        out.opt_u64(self.source_hash)?;
+        out.u8(self.source_stamp.is_some() as u8)?;
+        let stamp = self.source_stamp.unwrap_or(SourceStamp { size: 0, modified_secs: 0, modified_nanos: 0 });
+        out.u64(stamp.size)?;
+        out.u64(stamp.modified_secs)?;
+        out.u32(stamp.modified_nanos)?;
+        out.u64(self.discontinued as u64)?;
+        out.u8(match self.graph_mode {
+            GraphMode::Directed => 0,
+            GraphMode::Undirected => 1,
+            GraphMode::Mutual => 2,
+        })?;
+        out.u8(match self.unresolved {
+            UnresolvedNodes::Keep => 0,
+            UnresolvedNodes::Prune => 1,
+        })?;
+        out.u8(match self.parse_mode {
+            ParseMode::Strict => 0,
+            ParseMode::Lenient => 1,
+        })?;
+
+        let mut asins: Vec<&String> = self.products.keys().collect();
+        asins.sort_unstable();
+        out.u64(asins.len() as u64)?;
+        for asin in asins {
+            out.product(&self.products[asin])?;
+        }
+
+        out.u64(self.graph.node_count() as u64)?;
+        for node in self.graph.node_indices() {
+            out.str(&self.graph[node])?;
+        }
+        out.u64(self.graph.edge_count() as u64)?;
+        for edge in self.graph.raw_edges() {
+            out.u32(edge.source().index() as u32)?;
+            out.u32(edge.target().index() as u32)?;
+            out.u32(edge.weight.rank)?;
+            out.u8(edge.weight.reciprocal as u8)?;
+            out.u8(match edge.weight.kind {
+                LinkKind::Similar => 0,
+            })?;
+        }
+
+        let mut ids: Vec<(u32, &str)> = self.ids.iter().collect();
+        ids.sort_unstable();
+        out.u64(ids.len() as u64)?;
+        for (id, asin) in ids {
+            out.u32(id)?;
+            out.str(asin)?;
+        }
+
+        out.category_tree(&self.categories)?;
+        out.0.flush()?;
+        Ok(())
+    }
+
+    // Reads a dataset back from a snapshot written by 'save'
+    pub fn load_snapshot(path: &str) -> Result<ProductDataset, SnapshotError> {
+        let mut input = Decoder(BufReader::new(File::open(path)?));
+        let header = input.header()?;
+        input.dataset(header)
+    }
+
+/*
+    Like 'load_snapshot', but refuses a snapshot whose source file has changed since
+    The source is only hashed when its size or modification time differ from the ones
+    recorded in the snapshot, and before anything past the header is decoded
+*/
+    pub fn load_snapshot_for(path: &str, source_path: &str) -> Result<ProductDataset, SnapshotError> {
+        let mut input = Decoder(BufReader::new(File::open(path)?));
+        let header = input.header()?;
+        let source_path = Path::new(source_path);
+        let stamp = SourceStamp::from_metadata(&source_path.metadata()?);
+        let unchanged = header.source_hash.is_some() && stamp.is_some() && header.source_stamp == stamp;
+        if !unchanged {
+            let found = content_hash(source_path)?;
+            if header.source_hash != Some(found) {
+                return Err(SnapshotError::Stale { expected: header.source_hash, found });
+            }
+        }
+        input.dataset(header)
+    }
+}
+
+/*
+    Loads the dump through a snapshot cache
+    Uses the snapshot when it matches the source file, otherwise parses the source and
+    rewrites the snapshot. A snapshot that can't be written only produces a warning.
+*/
+pub fn load_cached(
+    file_path: &str,
+    snapshot_path: &str,
+    options: &LoadOptions,
+) -> Result<ProductDataset, LoaderError> {
+    match ProductDataset::load_snapshot_for(snapshot_path, file_path) {
+        // A snapshot only stands in for a load with the same graph interpretation
+        Ok(dataset) if dataset.graph_mode != options.graph_mode => eprintln!(
+            "Ignoring snapshot {}: built as a {:?} graph, {:?} requested",
+            snapshot_path, dataset.graph_mode, options.graph_mode
+        ),
+        Ok(dataset) if dataset.unresolved != options.unresolved => eprintln!(
+            "Ignoring snapshot {}: placeholder nodes {:?}, {:?} requested",
+            snapshot_path, dataset.unresolved, options.unresolved
+        ),
+        // A lenient parse may have skipped lines a strict one has to fail on
+        Ok(dataset) if dataset.parse_mode != options.mode => eprintln!(
+            "Ignoring snapshot {}: parsed in {:?} mode, {:?} requested",
+            snapshot_path, dataset.parse_mode, options.mode
+        ),
+        Ok(dataset) => {
+            if !options.quiet {
+                println!("Loaded snapshot: {}", snapshot_path);
+            }
+            return Ok(dataset);
+        }
+        Err(SnapshotError::Io(err)) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => eprintln!("Ignoring snapshot {}: {}", snapshot_path, err),
+    }
+
+    let (dataset, _report) = loader::load_dataset_with_options(file_path, options)?;
+    if let Err(err) = dataset.save(snapshot_path) {
+        eprintln!("Could not write snapshot {}: {}", snapshot_path, err);
+    }
+    Ok(dataset)
+}
+
+struct Encoder<W>(W);
+
+impl<W: Write> Encoder<W> {
+    fn bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.0.write_all(bytes)
+    }
+
+    fn u8(&mut self, v: u8) -> io::Result<()> {
+        self.bytes(&[v])
+    }
+
+    fn u16(&mut self, v: u16) -> io::Result<()> {
+        self.bytes(&v.to_le_bytes())
+    }
+
+    fn u32(&mut self, v: u32) -> io::Result<()> {
+        self.bytes(&v.to_le_bytes())
+    }
+
+    fn u64(&mut self, v: u64) -> io::Result<()> {
+        self.bytes(&v.to_le_bytes())
+    }
+
+    fn str(&mut self, s: &str) -> io::Result<()> {
+        self.u32(s.len() as u32)?;
+        self.bytes(s.as_bytes())
+    }
+
+    fn opt_u32(&mut self, v: Option<u32>) -> io::Result<()> {
+        self.u8(v.is_some() as u8)?;
+        self.u32(v.unwrap_or(0))
+    }
+
+    fn opt_u64(&mut self, v: Option<u64>) -> io::Result<()> {
+        self.u8(v.is_some() as u8)?;
+        self.u64(v.unwrap_or(0))
+    }
+
+    fn product(&mut self, p: &Product) -> io::Result<()> {
+        self.opt_u32(p.id)?;
+        self.str(&p.asin)?;
+        self.str(&p.title)?;
+        self.str(&p.group)?;
+        self.bytes(&p.salesrank.to_le_bytes())?;
+        self.u8(match p.status {
+            ProductStatus::Active => 0,
+            ProductStatus::Discontinued => 1,
+        })?;
+
+        self.u32(p.similar.len() as u32)?;
+        for asin in &p.similar {
+            self.str(asin)?;
+        }
+
+        self.u32(p.categories.len() as u32)?;
+        for path in &p.categories {
+            self.u32(path.len() as u32)?;
+            for category in path {
+                self.str(&category.name)?;
+                self.u32(category.id)?;
+            }
+        }
+
+        self.u32(p.review_summary.total)?;
+        self.u32(p.review_summary.downloaded)?;
+        self.bytes(&p.review_summary.avg_rating.to_le_bytes())?;
+        self.u32(p.reviews.len() as u32)?;
+        for review in &p.reviews {
+            self.u16(review.date.year)?;
+            self.u8(review.date.month)?;
+            self.u8(review.date.day)?;
+            self.str(&review.customer)?;
+            self.u8(review.rating)?;
+            self.u32(review.votes)?;
+            self.u32(review.helpful)?;
+        }
+        Ok(())
+    }
+
+    fn category_tree(&mut self, tree: &CategoryTree) -> io::Result<()> {
+        let mut ids: Vec<&u32> = tree.nodes.keys().collect();
+        ids.sort_unstable();
+        self.u64(ids.len() as u64)?;
+        for id in ids {
+            let node = &tree.nodes[id];
+            self.u32(node.id)?;
+            self.str(&node.name)?;
+            self.opt_u32(node.parent)?;
+            self.u32(node.children.len() as u32)?;
+            for child in &node.children {
+                self.u32(*child)?;
+            }
+            self.u32(node.products.len() as u32)?;
+            for asin in &node.products {
+                self.str(asin)?;
+            }
+        }
+
+        self.u64(tree.roots.len() as u64)?;
+        for root in &tree.roots {
+            self.u32(*root)?;
+        }
+
+        let mut paths: Vec<(&String, &Vec<Vec<u32>>)> = tree.product_paths.iter().collect();
+        paths.sort_unstable();
+        self.u64(paths.len() as u64)?;
+        for (asin, paths) in paths {
+            self.str(asin)?;
+            self.u32(paths.len() as u32)?;
+            for path in paths {
+                self.u32(path.len() as u32)?;
+                for id in path {
+                    self.u32(*id)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Decoder<R>(R);
+
+impl<R: Read> Decoder<R> {
+    fn array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.0.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn str(&mut self) -> Result<String, SnapshotError> {
+        let len = self.u32()? as usize;
+        // Read through 'take' so a corrupt length can't trigger a huge allocation
+        let mut buf = Vec::new();
+        (&mut self.0).take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() != len {
+            return Err(SnapshotError::Corrupt("unexpected end of file"));
+        }
+        String::from_utf8(buf).map_err(|_| SnapshotError::Corrupt("string is not UTF-8"))
+    }
+
+    fn opt_u32(&mut self) -> io::Result<Option<u32>> {
+        let present = self.u8()? != 0;
+        let v = self.u32()?;
+        Ok(present.then_some(v))
+    }
+
+    fn opt_u64(&mut self) -> io::Result<Option<u64>> {
+        let present = self.u8()? != 0;
+        let v = self.u64()?;
+        Ok(present.then_some(v))
+    }
+
+    fn product(&mut self) -> Result<Product, SnapshotError> {
+        let id = self.opt_u32()?;
+        let asin = self.str()?;
+        let title = self.str()?;
+        let group = self.str()?;
+        let salesrank = i32::from_le_bytes(self.array()?);
+        let status = match self.u8()? {
+            0 => ProductStatus::Active,
+            1 => ProductStatus::Discontinued,
+            _ => return Err(SnapshotError::Corrupt("unknown product status")),
+        };
+
+        let mut similar = Vec::new();
+        for _ in 0..self.u32()? {
+            similar.push(self.str()?);
+        }
+
+        let mut categories = Vec::new();
+        for _ in 0..self.u32()? {
+            let mut path = Vec::new();
+            for _ in 0..self.u32()? {
+                let name = self.str()?;
+                path.push(Category { name, id: self.u32()? });
+            }
+            categories.push(path);
+        }
+
+        let review_summary = ReviewSummary {
+            total: self.u32()?,
+            downloaded: self.u32()?,
+            avg_rating: f32::from_le_bytes(self.array()?),
+        };
+        let mut reviews = Vec::new();
+        for _ in 0..self.u32()? {
+            let date = ReviewDate { year: self.u16()?, month: self.u8()?, day: self.u8()? };
+            reviews.push(Review {
+                date,
+                customer: self.str()?,
+                rating: self.u8()?,
+                votes: self.u32()?,
+                helpful: self.u32()?,
+            });
+        }
+
+        Ok(Product { id, asin, title, group, salesrank, similar, categories, review_summary, reviews, status })
+    }
+
+    fn category_tree(&mut self) -> Result<CategoryTree, SnapshotError> {
+        let mut tree = CategoryTree::default();
+        for _ in 0..self.u64()? {
+            let id = self.u32()?;
+            let name = self.str()?;
+            let parent = self.opt_u32()?;
+            let mut children = Vec::new();
+            for _ in 0..self.u32()? {
+                children.push(self.u32()?);
+            }
+            let mut products = Vec::new();
+            for _ in 0..self.u32()? {
+                products.push(self.str()?);
+            }
+            tree.nodes.insert(id, CategoryNode { id, name, parent, children, products });
+        }
+
+        for _ in 0..self.u64()? {
+            tree.roots.push(self.u32()?);
+        }
+
+        for _ in 0..self.u64()? {
+            let asin = self.str()?;
+            let mut paths = Vec::new();
+            for _ in 0..self.u32()? {
+                let mut ids = Vec::new();
+                for _ in 0..self.u32()? {
+                    ids.push(self.u32()?);
+                }
+                paths.push(ids);
+            }
+            tree.product_paths.insert(asin, paths);
+        }
+        Ok(tree)
+    }
+
+    fn header(&mut self) -> Result<Header, SnapshotError> {
+        let mut magic = [0u8; 8];
+        self.0.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+        let version = self.u32()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let source_hash = self.opt_u64()?;
+        let has_stamp = self.u8()? != 0;
+        let stamp = SourceStamp { size: self.u64()?, modified_secs: self.u64()?, modified_nanos: self.u32()? };
+        let discontinued = self.u64()? as usize;
+        let graph_mode = match self.u8()? {
+            0 => GraphMode::Directed,
+            1 => GraphMode::Undirected,
+            2 => GraphMode::Mutual,
+            _ => return Err(SnapshotError::Corrupt("unknown graph mode")),
+        };
+        let unresolved = match self.u8()? {
+            0 => UnresolvedNodes::Keep,
+            1 => UnresolvedNodes::Prune,
+            _ => return Err(SnapshotError::Corrupt("unknown unresolved node handling")),
+        };
+        let parse_mode = match self.u8()? {
+            0 => ParseMode::Strict,
+            1 => ParseMode::Lenient,
+            _ => return Err(SnapshotError::Corrupt("unknown parse mode")),
+        };
+        Ok(Header {
+            source_hash,
+            source_stamp: has_stamp.then_some(stamp),
+            discontinued,
+            graph_mode,
+            unresolved,
+            parse_mode,
+        })
+    }
+
+    // Decodes everything after the header
+    fn dataset(&mut self, header: Header) -> Result<ProductDataset, SnapshotError> {
+        let product_count = self.u64()? as usize;
+        // Counts come straight from the file, so nothing is preallocated from them
+        let mut products = HashMap::new();
+        for _ in 0..product_count {
+            let product = self.product()?;
+            products.insert(product.asin.clone(), product);
+        }
+
+        let node_count = self.u64()? as usize;
+        let mut graph = Graph::new();
+        for _ in 0..node_count {
+            graph.add_node(self.str()?);
+        }
+        let edge_count = self.u64()?;
+        for _ in 0..edge_count {
+            let (source, target) = (self.u32()? as usize, self.u32()? as usize);
+            if source >= node_count || target >= node_count {
+                return Err(SnapshotError::Corrupt("edge points past the node list"));
+            }
+            let rank = self.u32()?;
+            let reciprocal = self.u8()? != 0;
+            let kind = match self.u8()? {
+                0 => LinkKind::Similar,
+                _ => return Err(SnapshotError::Corrupt("unknown link kind")),
+            };
+            let link = SimilarLink { rank, reciprocal, kind };
+            graph.add_edge(NodeIndex::new(source), NodeIndex::new(target), link);
+        }
+
+        let mut ids = IdIndex::default();
+        for _ in 0..self.u64()? {
+            let id = self.u32()?;
+            ids.insert(id, &self.str()?);
+        }
+
+        let categories = self.category_tree()?;
+        Ok(ProductDataset {
+            graph,
+            graph_mode: header.graph_mode,
+            unresolved: header.unresolved,
+            parse_mode: header.parse_mode,
+            products,
+            categories,
+            ids,
+            discontinued: header.discontinued,
+            source_hash: header.source_hash,
+            source_stamp: header.source_stamp,
+        })
+    }
+}
@@ -0,0 +1,207 @@
+// Module for structural statistics of the product graph
+
+use petgraph::Direction;
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::loader::{GraphMode, ProductGraph};
+
+// Largest graph whose diameter is computed exactly, and the sweeps used beyond that
+const EXACT_DIAMETER_NODES: usize = 5_000;
+const DIAMETER_SWEEPS: usize = 16;
+
+// Degree histogram, degree -> number of nodes with it
+pub type DegreeDistribution = BTreeMap<usize, usize>;
+
+/*
+    Structural statistics of a product graph, see 'graph_stats'
+    Degrees follow 'DegreeKind': in undirected and mutual graphs every kind is the plain
+    number of linked products. Clustering and diameter always read links as undirected.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphStats {
+    pub graph_mode: GraphMode,
+    pub nodes: usize,
+    pub edges: usize,
+    pub links: usize,             // Edges as links; undirected links are stored as two edges
+    pub avg_degree: f64,          // Average out-degree, 0 for an empty graph
+    pub in_degrees: DegreeDistribution,
+    pub out_degrees: DegreeDistribution,
+    pub total_degrees: DegreeDistribution,
+    pub max_in_degree: usize,
+    pub max_out_degree: usize,
+    pub max_total_degree: usize,
+    pub density: f64,             // Edges over the n * (n - 1) possible ones
+    pub reciprocity: f64,         // Share of listed links the other product lists back (1 in mutual graphs)
+    pub global_clustering: f64,   // Transitivity: closed triples over all connected triples
+    pub avg_clustering: f64,      // Mean local clustering coefficient over all nodes
+    pub diameter: usize,          // Longest shortest path within any component
+    pub diameter_exact: bool,     // False when 'diameter' is a lower bound from sampled sweeps
+    pub self_loops: usize,
+    pub dangling_nodes: usize,    // Nodes without outgoing links, e.g. ASINs without a record
+}
+
+pub fn graph_stats(graph: &ProductGraph, graph_mode: GraphMode) -> GraphStats {
+    let nodes = graph.node_count();
+    let edges = graph.edge_count();
+
+    let mut in_degrees = DegreeDistribution::new();
+    let mut out_degrees = DegreeDistribution::new();
+    let mut total_degrees = DegreeDistribution::new();
+    let mut dangling_nodes = 0;
+    for node in graph.node_indices() {
+        let incoming = graph.neighbors_directed(node, Direction::Incoming).count();
+        let outgoing = graph.neighbors_directed(node, Direction::Outgoing).count();
+        let (incoming, total) = match graph_mode {
+            GraphMode::Directed => (incoming, incoming + outgoing),
+            GraphMode::Undirected | GraphMode::Mutual => (outgoing, outgoing),
+        };
+        *in_degrees.entry(incoming).or_default() += 1;
+        *out_degrees.entry(outgoing).or_default() += 1;
+        *total_degrees.entry(total).or_default() += 1;
+        if outgoing == 0 {
+            dangling_nodes += 1;
+        }
+    }
+    let max_degree = |distribution: &DegreeDistribution| distribution.keys().next_back().copied().unwrap_or(0);
+
+    let self_loops = graph.raw_edges().iter().filter(|e| e.source() == e.target()).count();
+    // Read from 'SimilarLink::reciprocal' rather than reverse edges, which undirected graphs
+    // add for every link; the two edges they store for a one-way link count half each
+    let (mut listed, mut reciprocated) = (0.0, 0.0);
+    for edge in graph.raw_edges().iter().filter(|e| e.source() != e.target()) {
+        if edge.weight.reciprocal {
+            listed += 1.0;
+            reciprocated += 1.0;
+        } else {
+            listed += if graph_mode == GraphMode::Undirected { 0.5 } else { 1.0 };
+        }
+    }
+
+    let neighbors = undirected_neighbors(graph);
+    let (global_clustering, avg_clustering) = clustering(&neighbors);
+    let (diameter, diameter_exact) = diameter(&neighbors);
+
+    let ratio = |part: f64, whole: f64| if whole == 0.0 { 0.0 } else { part / whole };
+    GraphStats {
+        graph_mode,
+        nodes,
+        edges,
+        links: graph_mode.link_count(graph),
+        avg_degree: ratio(edges as f64, nodes as f64),
+        max_in_degree: max_degree(&in_degrees),
+        max_out_degree: max_degree(&out_degrees),
+        max_total_degree: max_degree(&total_degrees),
+        in_degrees,
+        out_degrees,
+        total_degrees,
+        density: ratio((edges - self_loops) as f64, nodes as f64 * nodes.saturating_sub(1) as f64),
+        reciprocity: ratio(reciprocated, listed),
+        global_clustering,
+        avg_clustering,
+        diameter,
+        diameter_exact,
+        self_loops,
+        dangling_nodes,
+    }
+}
+
+// Sorted neighbor lists ignoring direction, self-loops and duplicate edges
+fn undirected_neighbors(graph: &ProductGraph) -> Vec<Vec<usize>> {
+    let mut neighbors = vec![Vec::new(); graph.node_count()];
+    for edge in graph.raw_edges() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a != b {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+    }
+    for list in &mut neighbors {
+        list.sort_unstable();
+        list.dedup();
+    }
+    neighbors
+}
+
+// (transitivity, average local clustering coefficient)
+fn clustering(neighbors: &[Vec<usize>]) -> (f64, f64) {
+    let n = neighbors.len();
+    let mut marked = vec![false; n];
+    let mut closed = 0.0; // Linked neighbor pairs, summed over all nodes
+    let mut triples = 0.0; // Neighbor pairs, summed over all nodes
+    let mut local_sum = 0.0;
+
+    for v in 0..n {
+        let degree = neighbors[v].len();
+        if degree < 2 {
+            continue;
+        }
+        neighbors[v].iter().for_each(|&u| marked[u] = true);
+        let links: usize = neighbors[v].iter()
+            .map(|&u| neighbors[u].iter().filter(|&&w| w > u && marked[w]).count())
+            .sum();
+        neighbors[v].iter().for_each(|&u| marked[u] = false);
+
+        let pairs = (degree * (degree - 1) / 2) as f64;
+        closed += links as f64;
+        triples += pairs;
+        local_sum += links as f64 / pairs;
+    }
+
+    let global = if triples == 0.0 { 0.0 } else { closed / triples };
+    let average = if n == 0 { 0.0 } else { local_sum / n as f64 };
+    (global, average)
+}
+
+/*
+    Longest shortest path between two connected nodes, links read as undirected
+    Exact (a BFS from every node) up to 'EXACT_DIAMETER_NODES' nodes. Beyond that a
+    double sweep from evenly spread start nodes: BFS to the farthest node, then BFS
+    again from there, which gives a lower bound that is usually tight in practice.
+*/
+fn diameter(neighbors: &[Vec<usize>]) -> (usize, bool) {
+    let n = neighbors.len();
+    let mut dist = vec![usize::MAX; n];
+    let mut queue = VecDeque::new();
+
+    if n <= EXACT_DIAMETER_NODES {
+        let longest = (0..n)
+            .map(|v| eccentricity(neighbors, v, &mut dist, &mut queue).1)
+            .max()
+            .unwrap_or(0);
+        return (longest, true);
+    }
+
+    let mut longest = 0;
+    for sweep in 0..DIAMETER_SWEEPS {
+        let start = sweep * n / DIAMETER_SWEEPS;
+        let (far, _) = eccentricity(neighbors, start, &mut dist, &mut queue);
+        longest = longest.max(eccentricity(neighbors, far, &mut dist, &mut queue).1);
+    }
+    (longest, false)
+}
+
+// BFS from 'source': (farthest node, its distance)
+fn eccentricity(
+    neighbors: &[Vec<usize>],
+    source: usize,
+    dist: &mut [usize],
+    queue: &mut VecDeque<usize>,
+) -> (usize, usize) {
+    dist.fill(usize::MAX);
+    dist[source] = 0;
+    queue.push_back(source);
+    let mut farthest = (source, 0);
+    while let Some(v) = queue.pop_front() {
+        if dist[v] > farthest.1 {
+            farthest = (v, dist[v]);
+        }
+        for &u in &neighbors[v] {
+            if dist[u] == usize::MAX {
+                dist[u] = dist[v] + 1;
+                queue.push_back(u);
+            }
+        }
+    }
+    farthest
+}
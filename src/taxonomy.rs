@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use serde::Serialize;
+
+use crate::loader::Product;
+
+// One step of a category path, e.g. 'Books[283155]'
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct Category {
+    pub name: String,
+    pub id: u32,
+}
+
+// A node in the category taxonomy along with the products filed under it.
+// The same category id can turn up under several parents (e.g. 'Cooking' under both
+// 'Subjects' and 'Specialty'); it is then a child of each of them.
+#[derive(Debug, Clone)]
+pub struct CategoryNode {
+    pub id: u32,
+    pub name: String,
+    pub parent: Option<u32>, // First parent seen, see 'CategoryTree::parents' for all of them
+    pub children: Vec<u32>,
+    pub products: Vec<String>, // Every product listing a path through this category
+}
+
+// Category taxonomy built from the paths of every loaded product
+#[derive(Debug, Clone, Default)]
+pub struct CategoryTree {
+    pub(crate) nodes: HashMap<u32, CategoryNode>,
+    pub(crate) roots: Vec<u32>,
+    // Category ids of every distinct path each product lists, root first
+    pub(crate) product_paths: HashMap<String, Vec<Vec<u32>>>,
+}
+
+// Parses one '|Books[283155]|Subjects[1000]|...' line into its (name, id) steps
+pub fn parse_category_path(line: &str) -> Option<Vec<Category>> {
+    line.split('|')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let open = part.rfind('[')?;
+            let id = part[open + 1..].strip_suffix(']')?.parse().ok()?;
+            Some(Category {
+                name: part[..open].to_string(),
+                id,
+            })
+        })
+        .collect()
+}
+
+impl CategoryTree {
+    // Files a product under every category on every path it lists. Every parent a
+    // category is seen under gets it as a child, so each path stays walkable.
+    pub fn insert_product(&mut self, product: &Product) {
+        for path in &product.categories {
+            let mut parent = None;
+            for category in path {
+                let node = self.nodes.entry(category.id).or_insert_with(|| CategoryNode {
+                    id: category.id,
+                    name: category.name.clone(),
+                    parent,
+                    children: Vec::new(),
+                    products: Vec::new(),
+                });
+                // A product's categories are all filed in one go, so checking the last one is enough
+                if node.products.last() != Some(&product.asin) {
+                    node.products.push(product.asin.clone());
+                }
+                match parent.and_then(|p| self.nodes.get_mut(&p)) {
+                    Some(parent_node) if !parent_node.children.contains(&category.id) => {
+                        parent_node.children.push(category.id);
+                    }
+                    Some(_) => {}
+                    None if !self.roots.contains(&category.id) => self.roots.push(category.id),
+                    None => {}
+                }
+                parent = Some(category.id);
+            }
+
+            let ids: Vec<u32> = path.iter().map(|category| category.id).collect();
+            let paths = self.product_paths.entry(product.asin.clone()).or_default();
+            if !ids.is_empty() && !paths.contains(&ids) {
+                paths.push(ids);
+            }
+        }
+    }
+
+    pub fn node(&self, id: u32) -> Option<&CategoryNode> {
+        self.nodes.get(&id)
+    }
+
+    pub fn roots(&self) -> &[u32] {
+        &self.roots
+    }
+
+    // Every category the given one was seen directly under, sorted
+    pub fn parents(&self, id: u32) -> Vec<u32> {
+        let mut parents: Vec<u32> = self.nodes.values()
+            .filter(|node| node.children.contains(&id))
+            .map(|node| node.id)
+            .collect();
+        parents.sort_unstable();
+        parents
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // All ASINs listing a path through the category, so also those filed under any of
+    // its descendants along that path, sorted
+    pub fn products_under(&self, id: u32) -> Vec<&str> {
+        let Some(node) = self.nodes.get(&id) else {
+            return Vec::new();
+        };
+        let mut asins: Vec<&str> = node.products.iter().map(String::as_str).collect();
+        asins.sort_unstable();
+        asins
+    }
+
+    // Breadcrumb strings ('Books > Subjects > ...') for each category path of a product,
+    // exactly as the product lists them
+    pub fn breadcrumbs(&self, asin: &str) -> Vec<String> {
+        let Some(paths) = self.product_paths.get(asin) else {
+            return Vec::new();
+        };
+
+        paths.iter()
+            .map(|path| {
+                path.iter()
+                    .filter_map(|id| self.nodes.get(id))
+                    .map(|node| node.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" > ")
+            })
+            .collect()
+    }
+
+    // Number of levels on the longest category path any product lists (0 for an empty
+    // tree). Read off the paths themselves, since shared categories can join paths up.
+    pub fn depth(&self) -> usize {
+        self.product_paths.values().flatten().map(Vec::len).max().unwrap_or(0)
+    }
+}
@@ -0,0 +1,488 @@
+use amazon_trends::{loader::{GraphMode, Product, ProductDataset, ProductStatus, SimilarLink}, analyzer::{DegreeKind, HopDirection, ProductAnalyzer}, paths::PathError};
+use petgraph::graph::Graph;
+use std::collections::HashMap;
+
+#[test]
+fn test_top_products_by_connections() {
+    let dataset = create_connected_test_data();
+    let analyzer = ProductAnalyzer::new(&dataset);
+    
+    let top = analyzer.top_products_by_connections(2, DegreeKind::Out);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0].asin, "PROD1"); // Most connected (2 edges)
+    assert_eq!(top[1].asin, "PROD2"); // Second most (1 edge)
+}
+
+#[test]
+fn test_degree_kinds_and_tie_break() {
+    let dataset = create_connected_test_data();
+    let analyzer = ProductAnalyzer::new(&dataset);
+
+    // PROD3 is listed by both others but lists nothing itself
+    let most_recommended = analyzer.top_products_by_connections(3, DegreeKind::In);
+    let asins: Vec<_> = most_recommended.iter().map(|p| p.asin.as_str()).collect();
+    assert_eq!(asins, ["PROD3", "PROD2", "PROD1"]);
+
+    // Every product has two links in total, so sales rank decides
+    let total = analyzer.top_products_by_connections(3, DegreeKind::Total);
+    let asins: Vec<_> = total.iter().map(|p| p.asin.as_str()).collect();
+    assert_eq!(asins, ["PROD3", "PROD1", "PROD2"]);
+}
+
+#[test]
+fn test_pagerank() {
+    let dataset = create_connected_test_data();
+    let analyzer = ProductAnalyzer::new(&dataset);
+
+    let scores = analyzer.pagerank(0.85, 1e-9, 100);
+    assert_eq!(scores.len(), 3);
+    assert!((scores.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(scores["PROD3"] > scores["PROD2"] && scores["PROD2"] > scores["PROD1"]);
+
+    let top = analyzer.top_products_by_pagerank(2, 0.85, 1e-9, 100);
+    let asins: Vec<_> = top.iter().map(|(p, _)| p.asin.as_str()).collect();
+    assert_eq!(asins, ["PROD3", "PROD2"]);
+    assert_eq!(top[0].1, scores["PROD3"]);
+}
+
+#[test]
+fn test_betweenness_and_closeness() {
+    // Two pairs of products that only connect through BRIDGE
+    let mut graph = Graph::new();
+    let mut products = HashMap::new();
+    let asins = ["A1", "A2", "BRIDGE", "B1", "B2"];
+    let nodes: Vec<_> = asins.iter().map(|asin| graph.add_node(asin.to_string())).collect();
+    for (from, to) in [(0, 1), (1, 0), (1, 2), (2, 1), (2, 3), (3, 2), (3, 4), (4, 3)] {
+        graph.add_edge(nodes[from], nodes[to], SimilarLink::default());
+    }
+    for (i, asin) in asins.iter().enumerate() {
+        products.insert(asin.to_string(), product(asin, 100 * (i as i32 + 1)));
+    }
+    let mut dataset = ProductDataset { graph, products, ..Default::default() };
+
+    let analyzer = ProductAnalyzer::new(&dataset);
+    let betweenness = analyzer.betweenness(None);
+    assert_eq!(betweenness["BRIDGE"], 8.0);
+    assert_eq!(betweenness["A2"], 6.0);
+    assert_eq!(betweenness["A1"], 0.0);
+    let (top, score) = analyzer.top_products_by_betweenness(1, None)[0];
+    assert_eq!((top.asin.as_str(), score), ("BRIDGE", 8.0));
+    // Sampling every node is the exact computation
+    assert_eq!(analyzer.betweenness(Some(5)), betweenness);
+    assert_eq!(analyzer.top_products_by_betweenness(1, Some(3))[0].0.asin, "BRIDGE");
+
+    let closeness = analyzer.closeness(None);
+    assert!((closeness["BRIDGE"] - 4.0 / 6.0).abs() < 1e-12);
+    assert!((closeness["A1"] - 4.0 / 10.0).abs() < 1e-12);
+    assert!(analyzer.closeness(Some(3))["BRIDGE"] > 0.0);
+    let (top, score) = analyzer.top_products_by_closeness(1, None)[0];
+    assert_eq!((top.asin.as_str(), score), ("BRIDGE", closeness["BRIDGE"]));
+
+    // The same links read as undirected count every pair once
+    dataset.graph_mode = GraphMode::Undirected;
+    let analyzer = ProductAnalyzer::new(&dataset);
+    assert_eq!(analyzer.betweenness(None)["BRIDGE"], 4.0);
+    assert_eq!(analyzer.top_products_by_betweenness(1, None)[0].1, 4.0);
+}
+
+#[test]
+fn test_detect_communities() {
+    // Two dense groups of four joined by a single link
+    let mut graph = Graph::new();
+    let mut products = HashMap::new();
+    let nodes: Vec<_> = (0..8).map(|i| graph.add_node(format!("PROD{}", i))).collect();
+    for group in [0..4, 4..8] {
+        for a in group.clone() {
+            for b in group.clone().filter(|&b| b != a) {
+                graph.add_edge(nodes[a], nodes[b], SimilarLink::default());
+            }
+        }
+    }
+    graph.add_edge(nodes[3], nodes[4], SimilarLink::default());
+    for i in 0..8 {
+        let mut p = product(&format!("PROD{}", i), 100 + i);
+        p.group = if i < 4 || i == 7 { "Book" } else { "Music" }.to_string();
+        products.insert(p.asin.clone(), p);
+    }
+    let dataset = ProductDataset { graph, products, ..Default::default() };
+    let analyzer = ProductAnalyzer::new(&dataset);
+
+    let found = analyzer.detect_communities(2);
+    assert_eq!(found.communities.len(), 2);
+    assert!(found.modularity > 0.4);
+    let first: Vec<_> = found.communities[0].products.iter().map(|p| p.asin.as_str()).collect();
+    assert_eq!(first, ["PROD0", "PROD1", "PROD2", "PROD3"]);
+    assert_eq!(found.communities[0].dominant_group, Some("Book"));
+    assert_eq!(found.communities[1].dominant_group, Some("Music"));
+    let total: f64 = found.communities.iter().map(|c| c.modularity).sum();
+    assert!((total - found.modularity).abs() < 1e-12);
+    assert!(analyzer.detect_communities(5).communities.is_empty());
+}
+
+#[test]
+fn test_community_weights_do_not_depend_on_graph_mode() {
+    // Two triangles joined by one link, with PROD0 and PROD1 listing each other
+    let links = [(0, 1), (1, 0), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)];
+    let modularity = |graph_mode| {
+        let mut graph = Graph::new();
+        let nodes: Vec<_> = (0..6).map(|i| graph.add_node(format!("PROD{}", i))).collect();
+        for &(a, b) in &links {
+            let link = SimilarLink { reciprocal: links.contains(&(b, a)), ..Default::default() };
+            graph.add_edge(nodes[a], nodes[b], link);
+            // Undirected graphs also store one-way links the other way round
+            if graph_mode == GraphMode::Undirected && !link.reciprocal {
+                graph.add_edge(nodes[b], nodes[a], link);
+            }
+        }
+        let products = (0..6).map(|i| product(&format!("PROD{}", i), 100)).map(|p| (p.asin.clone(), p)).collect();
+        let dataset = ProductDataset { graph, products, graph_mode, ..Default::default() };
+        let modularity = ProductAnalyzer::new(&dataset).detect_communities(1).modularity;
+        modularity
+    };
+    assert_eq!(modularity(GraphMode::Directed), modularity(GraphMode::Undirected));
+}
+
+#[test]
+fn test_weakly_connected_components_and_giant_component() {
+    let mut dataset = create_connected_test_data();
+    let lone = dataset.graph.add_node("LONE".to_string());
+    let pair = dataset.graph.add_node("PAIR".to_string());
+    dataset.graph.add_edge(pair, lone, SimilarLink::default());
+    let single = dataset.graph.add_node("SINGLE".to_string());
+    dataset.products.get_mut("PROD3").unwrap().group = "Book".to_string();
+    let analyzer = ProductAnalyzer::new(&dataset);
+
+    let components = analyzer.weakly_connected_components();
+    assert_eq!(components.len(), 3);
+    assert_eq!(components[0].len(), 3);
+    assert_eq!(components[2], [single]);
+    assert_eq!(analyzer.component_size_histogram().into_iter().collect::<Vec<_>>(), [(1, 1), (2, 1), (3, 1)]);
+
+    let giant = analyzer.giant_component().unwrap();
+    assert_eq!((giant.nodes, giant.edges), (3, 3));
+    assert_eq!(giant.node_share, 0.5);
+    assert_eq!(giant.edge_share, 0.75);
+    assert_eq!(giant.groups, [("Electronics", 2), ("Book", 1)]);
+
+    // Stored both ways, every link still counts once
+    let mirrors: Vec<_> = dataset.graph.raw_edges().iter().map(|e| (e.target(), e.source(), e.weight)).collect();
+    for (source, target, link) in mirrors {
+        dataset.graph.add_edge(source, target, link);
+    }
+    dataset.graph_mode = GraphMode::Undirected;
+    let giant = ProductAnalyzer::new(&dataset).giant_component().unwrap();
+    assert_eq!((giant.nodes, giant.edges), (3, 3));
+    assert_eq!(giant.edge_share, 0.75);
+}
+
+#[test]
+fn test_graph_stats() {
+    let mut dataset = create_clustered_test_data();
+    let dangling = dataset.graph.add_node("DANGLING".to_string());
+    dataset.graph.add_edge(0.into(), dangling, SimilarLink::default());
+    dataset.graph.add_edge(dangling, dangling, SimilarLink::default());
+    let analyzer = ProductAnalyzer::new(&dataset);
+
+    let stats = analyzer.graph_stats();
+    assert_eq!((stats.nodes, stats.edges, stats.links), (4, 6, 6));
+    assert_eq!(stats.avg_degree, 1.5);
+    assert_eq!(stats.max_out_degree, 3);
+    assert_eq!(stats.max_in_degree, 2);
+    assert_eq!(stats.out_degrees.get(&1), Some(&3));
+    assert_eq!(stats.density, 5.0 / 12.0);
+    // PROD1 -> PROD3 and back are the only reciprocated edges
+    assert_eq!(stats.reciprocity, 2.0 / 5.0);
+    // One triangle; PROD1 has three neighbors, two of them linked
+    assert_eq!(stats.global_clustering, 3.0 / 5.0);
+    assert_eq!(stats.avg_clustering, (1.0 / 3.0 + 1.0 + 1.0) / 4.0);
+    assert_eq!((stats.diameter, stats.diameter_exact), (2, true));
+    assert_eq!((stats.self_loops, stats.dangling_nodes), (1, 0));
+
+    // Mirroring the one-way links for an undirected graph leaves reciprocity as listed
+    let mirrors: Vec<_> = dataset.graph.raw_edges().iter()
+        .filter(|e| !e.weight.reciprocal && e.source() != e.target())
+        .map(|e| (e.target(), e.source(), e.weight))
+        .collect();
+    for (source, target, link) in mirrors {
+        dataset.graph.add_edge(source, target, link);
+    }
+    dataset.graph_mode = GraphMode::Undirected;
+    assert_eq!(ProductAnalyzer::new(&dataset).graph_stats().reciprocity, 2.0 / 5.0);
+
+    // Nothing divides by zero on an empty graph
+    let empty = ProductDataset::default();
+    let stats = ProductAnalyzer::new(&empty).graph_stats();
+    assert_eq!((stats.avg_degree, stats.density, stats.diameter), (0.0, 0.0, 0));
+}
+
+#[test]
+fn test_path_between_and_k_shortest_paths() {
+    let dataset = create_clustered_test_data();
+    let analyzer = ProductAnalyzer::new(&dataset);
+
+    let asins = |path: &[&Product]| path.iter().map(|p| p.asin.clone()).collect::<Vec<_>>();
+    assert_eq!(asins(&analyzer.path_between("PROD1", "PROD3").unwrap()), ["PROD1", "PROD3"]);
+    assert_eq!(asins(&analyzer.path_between("PROD2", "PROD1").unwrap()), ["PROD2", "PROD3", "PROD1"]);
+
+    let paths = analyzer.k_shortest_paths("PROD1", "PROD3", 5).unwrap();
+    assert_eq!(paths.len(), 2);
+    assert_eq!(asins(&paths[1]), ["PROD1", "PROD2", "PROD3"]);
+
+    let dataset = create_connected_test_data();
+    let analyzer = ProductAnalyzer::new(&dataset);
+    assert_eq!(
+        analyzer.path_between("PROD3", "PROD1").err(),
+        Some(PathError::NoPath { from: "PROD3".to_string(), to: "PROD1".to_string() })
+    );
+    assert_eq!(
+        analyzer.path_between("PROD1", "MISSING").err(),
+        Some(PathError::UnknownProduct("MISSING".to_string()))
+    );
+}
+
+#[test]
+fn test_paths_avoid_placeholders_in_undirected_graphs() {
+    // A and B both list GHOST, which has no record; C and D are a longer real route.
+    // Read as undirected every link is stored both ways, GHOST's included.
+    let mut graph = Graph::new();
+    let mut products = HashMap::new();
+    let asins = ["A", "B", "GHOST", "C", "D"];
+    let nodes: Vec<_> = asins.iter().map(|asin| graph.add_node(asin.to_string())).collect();
+    for (a, b) in [(0, 2), (1, 2), (0, 3), (3, 4), (4, 1)] {
+        graph.add_edge(nodes[a], nodes[b], SimilarLink::default());
+        graph.add_edge(nodes[b], nodes[a], SimilarLink::default());
+    }
+    for asin in ["A", "B", "C", "D"] {
+        products.insert(asin.to_string(), product(asin, 100));
+    }
+    let mut dataset = ProductDataset { graph, products, graph_mode: GraphMode::Undirected, ..Default::default() };
+
+    let analyzer = ProductAnalyzer::new(&dataset);
+    let asins = |path: &[&Product]| path.iter().map(|p| p.asin.clone()).collect::<Vec<_>>();
+    assert_eq!(asins(&analyzer.path_between("A", "B").unwrap()), ["A", "C", "D", "B"]);
+    assert_eq!(analyzer.k_shortest_paths("A", "B", 3).unwrap().len(), 1);
+
+    // Without the real route there is no chain at all
+    let c = dataset.graph.node_indices().find(|n| dataset.graph[*n] == "C").unwrap();
+    dataset.graph.retain_edges(|g, e| {
+        let (source, target) = g.edge_endpoints(e).unwrap();
+        source != c && target != c
+    });
+    let analyzer = ProductAnalyzer::new(&dataset);
+    assert_eq!(
+        analyzer.path_between("A", "B").err(),
+        Some(PathError::NoPath { from: "A".to_string(), to: "B".to_string() })
+    );
+}
+
+#[test]
+fn test_discontinued_products_excluded_by_default() {
+    let mut dataset = create_connected_test_data();
+    dataset.products.get_mut("PROD1").unwrap().status = ProductStatus::Discontinued;
+
+    let analyzer = ProductAnalyzer::new(&dataset);
+    assert_eq!(analyzer.top_products_by_connections(1, DegreeKind::Out)[0].asin, "PROD2");
+
+    let analyzer = ProductAnalyzer::new(&dataset).with_discontinued(true);
+    assert_eq!(analyzer.top_products_by_connections(1, DegreeKind::Out)[0].asin, "PROD1");
+}
+
+#[test]
+fn test_detect_trend_clusters() {
+    let dataset = create_clustered_test_data();
+    let analyzer = ProductAnalyzer::new(&dataset);
+
+    let clusters = analyzer.detect_trend_clusters(2);
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].len(), 3);
+    assert!(analyzer.detect_trend_clusters(4).is_empty());
+}
+
+#[test]
+fn test_weighted_connections_and_strong_clusters() {
+    let mut dataset = create_clustered_test_data();
+    // PROD1 lists PROD3 second, so that link is weaker than the rest
+    let edge = dataset.graph.find_edge(0.into(), 2.into()).unwrap();
+    dataset.graph[edge].rank = 2;
+    let analyzer = ProductAnalyzer::new(&dataset);
+
+    let top = analyzer.top_products_by_weighted_connections(3);
+    assert_eq!(top[0].0.asin, "PROD1");
+    assert_eq!(top[0].1, 1.5);
+
+    assert_eq!(analyzer.detect_strong_clusters(3, 0.5).len(), 1);
+    // Without the PROD1 -> PROD3 link the cycle still closes through PROD2
+    assert_eq!(analyzer.detect_strong_clusters(3, 1.0).len(), 1);
+    dataset.graph[edge].rank = 1;
+    let loop_edge = dataset.graph.find_edge(2.into(), 0.into()).unwrap();
+    dataset.graph[loop_edge].rank = 3;
+    let analyzer = ProductAnalyzer::new(&dataset);
+    assert!(analyzer.detect_strong_clusters(2, 0.5).is_empty());
+}
+
+#[test]
+fn test_undirected_graph_clusters_connected_products() {
+    let mut dataset = create_connected_test_data();
+    let analyzer = ProductAnalyzer::new(&dataset);
+    assert!(analyzer.detect_trend_clusters(2).is_empty());
+
+    // Mirror every link the way an undirected load stores it
+    let mirrors: Vec<_> = dataset.graph.raw_edges().iter().map(|e| (e.target(), e.source())).collect();
+    for (source, target) in mirrors {
+        dataset.graph.add_edge(source, target, SimilarLink::default());
+    }
+    dataset.graph_mode = GraphMode::Undirected;
+    let analyzer = ProductAnalyzer::new(&dataset);
+    assert_eq!(analyzer.detect_trend_clusters(3).len(), 1);
+    assert_eq!(analyzer.graph_mode.link_count(analyzer.graph), 3);
+}
+
+#[test]
+fn test_neighborhood() {
+    // FAN -> A -> B -> C -> D, and B lists an ASIN without a record
+    let mut graph = Graph::new();
+    let mut products = HashMap::new();
+    let asins = ["FAN", "A", "B", "C", "D"];
+    let nodes: Vec<_> = asins.iter().map(|asin| graph.add_node(asin.to_string())).collect();
+    let ghost = graph.add_node("GHOST".to_string());
+    for (from, to) in [(0, 1), (1, 2), (2, 3), (3, 4)] {
+        graph.add_edge(nodes[from], nodes[to], SimilarLink::default());
+    }
+    graph.add_edge(nodes[2], ghost, SimilarLink::default());
+    for (i, asin) in asins.iter().enumerate() {
+        let mut record = product(asin, 100 * (i as i32 + 1));
+        record.id = Some(i as u32);
+        products.insert(asin.to_string(), record);
+    }
+    products.get_mut("D").unwrap().status = ProductStatus::Discontinued;
+    let dataset = ProductDataset { graph, products, ..Default::default() };
+    let analyzer = ProductAnalyzer::new(&dataset);
+
+    let asins_of = |subset: &ProductDataset| -> Vec<String> {
+        subset.graph.node_indices().map(|n| subset.graph[n].clone()).collect()
+    };
+
+    let outgoing = analyzer.neighborhood("B", 2, HopDirection::Outgoing).unwrap();
+    assert_eq!(asins_of(&outgoing), ["B", "C", "D", "GHOST"]);
+    assert_eq!(outgoing.graph.edge_count(), 3);
+    assert_eq!(outgoing.products.len(), 3);
+    assert_eq!(outgoing.discontinued, 1);
+    assert_eq!(outgoing.product_by_id(3).map(|p| p.asin.as_str()), Some("C"));
+    assert!(outgoing.product_by_id(1).is_none());
+    assert_eq!(outgoing.unresolved_nodes().len(), 1);
+    assert_eq!(outgoing.source_hash, None);
+
+    let incoming = analyzer.neighborhood("B", 1, HopDirection::Incoming).unwrap();
+    assert_eq!(asins_of(&incoming), ["A", "B"]);
+
+    let ego = analyzer.neighborhood("B", 1, HopDirection::Both).unwrap();
+    assert_eq!(asins_of(&ego), ["A", "B", "C", "GHOST"]);
+    assert_eq!(ego.graph.edge_count(), 3);
+
+    // The subset is a dataset of its own, so other analyses run on it unchanged
+    let sub_analyzer = ProductAnalyzer::new(&ego);
+    assert_eq!(sub_analyzer.graph_stats().nodes, 4);
+    assert_eq!(sub_analyzer.path_between("A", "C").unwrap().len(), 3);
+    assert!(sub_analyzer.path_between("A", "D").is_err());
+
+    assert_eq!(analyzer.neighborhood("B", 0, HopDirection::Both).unwrap().graph.node_count(), 1);
+    assert!(analyzer.neighborhood("GHOST", 1, HopDirection::Both).is_none());
+}
+
+#[test]
+fn test_find_low_competition_products() {
+    let dataset = create_opportunity_test_data();
+    let analyzer = ProductAnalyzer::new(&dataset);
+    
+    let opportunities = analyzer.find_low_competition_products(1);
+    assert_eq!(opportunities.len(), 1);
+    assert_eq!(opportunities[0].0.asin, "PROD1"); // Best opportunity
+}
+
+#[test]
+fn test_empty_dataset() {
+    let dataset = ProductDataset {
+        graph: Graph::new(),
+        products: HashMap::new(),
+        ..Default::default()
+    };
+    let analyzer = ProductAnalyzer::new(&dataset);
+    
+    assert!(analyzer.top_products_by_connections(5, DegreeKind::Out).is_empty());
+    assert!(analyzer.detect_trend_clusters(2).is_empty());
+    assert!(analyzer.find_low_competition_products(5).is_empty());
+    assert!(analyzer.pagerank(0.85, 1e-6, 100).is_empty());
+    assert!(analyzer.detect_communities(1).communities.is_empty());
+    assert!(analyzer.weakly_connected_components().is_empty());
+    assert!(analyzer.giant_component().is_none());
+}
+
+// Helper functions
+fn create_connected_test_data() -> ProductDataset {
+    let mut graph = Graph::new();
+    let mut products = HashMap::new();
+    
+    let node1 = graph.add_node("PROD1".to_string());
+    let node2 = graph.add_node("PROD2".to_string());
+    let node3 = graph.add_node("PROD3".to_string());
+    
+    // PROD1 has 2 connections (most)
+    graph.add_edge(node1, node2, SimilarLink::default());
+    graph.add_edge(node1, node3, SimilarLink::default());
+    
+    // PROD2 has 1 connection
+    graph.add_edge(node2, node3, SimilarLink::default());
+    
+    products.insert("PROD1".to_string(), product("PROD1", 1000));
+    products.insert("PROD2".to_string(), product("PROD2", 2000));
+    products.insert("PROD3".to_string(), product("PROD3", 500));
+    
+    ProductDataset { graph, products, ..Default::default() }
+}
+
+fn create_clustered_test_data() -> ProductDataset {
+    let mut graph = Graph::new();
+    let mut products = HashMap::new();
+    
+    let node1 = graph.add_node("PROD1".to_string());
+    let node2 = graph.add_node("PROD2".to_string());
+    let node3 = graph.add_node("PROD3".to_string());
+    
+    // Fully connected cluster of 3
+    graph.add_edge(node1, node2, SimilarLink::default());
+    graph.add_edge(node2, node3, SimilarLink::default());
+    graph.add_edge(node1, node3, SimilarLink { reciprocal: true, ..Default::default() });
+    graph.add_edge(node3, node1, SimilarLink { reciprocal: true, ..Default::default() });
+    
+    products.insert("PROD1".to_string(), product("PROD1", 1000));
+    products.insert("PROD2".to_string(), product("PROD2", 2000));
+    products.insert("PROD3".to_string(), product("PROD3", 1500));
+    
+    ProductDataset { graph, products, ..Default::default() }
+}
+
+fn create_opportunity_test_data() -> ProductDataset {
+    let mut graph = Graph::new();
+    let mut products = HashMap::new();
+    
+    let node1 = graph.add_node("PROD1".to_string());
+    let node2 = graph.add_node("PROD2".to_string());
+    
+    // PROD1 has better rank and same connections as PROD2
+    graph.add_edge(node1, node2, SimilarLink::default());
+    
+    products.insert("PROD1".to_string(), product("PROD1", 500));  // Better rank
+    products.insert("PROD2".to_string(), product("PROD2", 1000));
+    
+    ProductDataset { graph, products, ..Default::default() }
+}
+
+fn product(asin: &str, salesrank: i32) -> Product {
+    Product {
+        asin: asin.to_string(),
+        title: format!("Test {}", asin),
+        group: "Electronics".to_string(),
+        salesrank,
+        ..Default::default()
+    }
+}
\ No newline at end of file
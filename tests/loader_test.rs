@@ -0,0 +1,478 @@
+use amazon_trends::loader::{
+    load_dataset, load_dataset_with_options, records, records_with_options, GraphMode, IdIndex, LinkKind, LoadOptions,
+    LoaderError, ParseMode,
+    Product, ProductDataset, ProductStatus, SimilarLink, UnresolvedNodes,
+};
+use amazon_trends::reviews::ReviewDate;
+use flate2::write::GzEncoder;
+use std::io::Write;
+use tempfile::NamedTempFile;
+use petgraph::graph::Graph;
+use std::collections::HashMap;
+
+#[test]
+fn test_load_empty_file() {
+    let file = NamedTempFile::new().unwrap();
+    let err = load_dataset(file.path().to_str().unwrap()).err().unwrap();
+    assert!(matches!(err, LoaderError::EmptyDataset { .. }));
+}
+
+#[test]
+fn test_load_malformed_data() {
+    // Create badly formatted data (missing ASIN)
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "title: Broken Product").unwrap();
+    file.flush().unwrap();
+    
+    assert!(load_dataset(file.path().to_str().unwrap()).is_err());
+}
+
+#[test]
+fn test_load_dataset_with_similar_products() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "ASIN: TEST1\n title: Test 1\n group: Book\n salesrank: 100\n similar: 2 TEST2 TEST3").unwrap();
+    writeln!(file, "\nASIN: TEST2\n title: Test 2\n group: Book").unwrap();
+    writeln!(file, "\nASIN: TEST3\n title: Test 3\n group: Book").unwrap();
+    file.flush().unwrap();
+    
+    let dataset = load_dataset(file.path().to_str().unwrap()).unwrap();
+    assert_eq!(dataset.graph.edge_count(), 2);
+}
+
+#[test]
+fn test_links_carry_rank_and_reciprocity() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Id:   1\nASIN: TEST1\n  similar: 2  TEST2  TEST3\n").unwrap();
+    writeln!(file, "Id:   2\nASIN: TEST2\n  similar: 1  TEST1").unwrap();
+
+    let dataset = load_dataset(file.path().to_str().unwrap()).unwrap();
+    let graph = &dataset.graph;
+    let link = |from: u32, to: u32| graph[graph.find_edge(from.into(), to.into()).unwrap()];
+    assert_eq!(link(0, 1), SimilarLink { rank: 1, reciprocal: true, kind: LinkKind::Similar });
+    assert_eq!(link(0, 2), SimilarLink { rank: 2, reciprocal: false, kind: LinkKind::Similar });
+    assert_eq!(link(1, 0).rank, 1);
+    assert!(link(1, 0).reciprocal);
+    assert_eq!(link(0, 2).weight(), 0.5);
+}
+
+#[test]
+fn test_graph_modes() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Id:   1\nASIN: TEST1\n  similar: 2  TEST2  TEST3\n").unwrap();
+    writeln!(file, "Id:   2\nASIN: TEST2\n  similar: 1  TEST1\n").unwrap();
+    writeln!(file, "Id:   3\nASIN: TEST3\n  similar: 1  TEST4").unwrap();
+
+    let load = |graph_mode| {
+        let options = LoadOptions { graph_mode, quiet: true, ..Default::default() };
+        load_dataset_with_options(file.path().to_str().unwrap(), &options).unwrap().0
+    };
+
+    let directed = load(GraphMode::Directed);
+    assert_eq!(directed.graph.edge_count(), 4);
+    assert_eq!(directed.graph_mode.link_count(&directed.graph), 4);
+
+    // The one mutual pair is already both ways; the other two links get mirrored
+    let undirected = load(GraphMode::Undirected);
+    assert_eq!(undirected.graph.edge_count(), 6);
+    assert_eq!(undirected.graph_mode.link_count(&undirected.graph), 3);
+    assert!(undirected.graph.find_edge(3.into(), 2.into()).is_some());
+
+    let mutual = load(GraphMode::Mutual);
+    assert_eq!(mutual.graph.edge_count(), 2);
+    assert_eq!(mutual.graph.node_count(), 4);
+    assert!(mutual.graph.edge_weights().all(|link| link.reciprocal));
+}
+
+#[test]
+fn test_self_loops_count_as_one_link() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Id:   1\nASIN: A\n  similar: 2  A  B").unwrap();
+    file.flush().unwrap();
+
+    let options = LoadOptions { graph_mode: GraphMode::Undirected, quiet: true, ..Default::default() };
+    let dataset = load_dataset_with_options(file.path().to_str().unwrap(), &options).unwrap().0;
+    assert_eq!(dataset.graph.edge_count(), 3);
+    assert_eq!(dataset.graph_mode.link_count(&dataset.graph), 2);
+
+    // A self-loop is its own reverse link, so mutual graphs keep it
+    let options = LoadOptions { graph_mode: GraphMode::Mutual, quiet: true, ..Default::default() };
+    let dataset = load_dataset_with_options(file.path().to_str().unwrap(), &options).unwrap().0;
+    assert_eq!(dataset.graph.edge_count(), 1);
+    assert!(dataset.graph.raw_edges()[0].weight.reciprocal);
+    assert_eq!(dataset.graph_mode.link_count(&dataset.graph), 1);
+}
+
+#[test]
+fn test_unresolved_nodes_kept_or_pruned() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Id:   1\nASIN: TEST1\n  similar: 3  TEST2  GONE1  GONE2\n").unwrap();
+    writeln!(file, "Id:   2\nASIN: TEST2\n  similar: 2  GONE1  TEST1").unwrap();
+    let path = file.path().to_str().unwrap();
+
+    let (kept, report) = load_dataset_with_options(path, &LoadOptions { quiet: true, ..Default::default() }).unwrap();
+    assert_eq!(report.outside_links, 3);
+    assert_eq!(report.dangling_similar, ["GONE1", "GONE2"]);
+    assert_eq!(kept.graph.node_count(), 4);
+    let unresolved: Vec<_> = kept.unresolved_nodes().into_iter().map(|n| kept.graph[n].as_str()).collect();
+    assert_eq!(unresolved, ["GONE1", "GONE2"]);
+
+    let options = LoadOptions { unresolved: UnresolvedNodes::Prune, quiet: true, ..Default::default() };
+    let (pruned, report) = load_dataset_with_options(path, &options).unwrap();
+    assert_eq!(report.outside_links, 3);
+    assert_eq!(pruned.unresolved, UnresolvedNodes::Prune);
+    assert!(pruned.unresolved_nodes().is_empty());
+    let nodes: Vec<_> = pruned.graph.node_weights().map(String::as_str).collect();
+    assert_eq!(nodes, ["TEST1", "TEST2"]);
+    assert_eq!(pruned.graph.edge_count(), 2);
+}
+
+#[test]
+fn test_records_streams_products() {
+    let data = "ASIN: TEST1\n  title: Test 1\n  salesrank: 100\n  similar: 1  TEST2\nASIN: TEST2\n  title: Test 2\n  salesrank: abc\n";
+    let parsed: Vec<Product> = records(data.as_bytes()).collect::<Result<_, _>>().unwrap();
+
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].asin, "TEST1");
+    assert_eq!(parsed[0].similar, vec!["TEST2".to_string()]);
+    assert_eq!(parsed[1].title, "Test 2");
+    assert_eq!(parsed[1].salesrank, -1);
+}
+
+#[test]
+fn test_load_dataset_builds_category_tree() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "ASIN: TEST1\n  title: Test 1\n  similar: 0\n  categories: 2").unwrap();
+    writeln!(file, "   |Books[283155]|Subjects[1000]|Cooking[6]\n   |Books[283155]|Subjects[1000]|Travel[27]").unwrap();
+    writeln!(file, "ASIN: TEST2\n  title: Test 2\n  similar: 0\n  categories: 1\n   |Books[283155]|Subjects[1000]|Travel[27]").unwrap();
+    file.flush().unwrap();
+
+    let dataset = load_dataset(file.path().to_str().unwrap()).unwrap();
+    assert_eq!(dataset.products["TEST1"].categories.len(), 2);
+    assert_eq!(dataset.products["TEST1"].categories[0][2].name, "Cooking");
+    assert_eq!(dataset.categories.depth(), 3);
+    assert_eq!(dataset.categories.products_under(1000), vec!["TEST1", "TEST2"]);
+    assert_eq!(dataset.categories.products_under(6), vec!["TEST1"]);
+    assert_eq!(
+        dataset.categories.breadcrumbs("TEST1"),
+        vec!["Books > Subjects > Cooking", "Books > Subjects > Travel"]
+    );
+}
+
+#[test]
+fn test_category_reached_through_two_parents() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "ASIN: A\n  categories: 2").unwrap();
+    writeln!(file, "   |Books[283155]|Subjects[1000]|Cooking[6]\n   |Books[283155]|Specialty[2000]|Cooking[6]").unwrap();
+    writeln!(file, "ASIN: B\n  categories: 1\n   |Books[283155]|Specialty[2000]|Cooking[6]").unwrap();
+    file.flush().unwrap();
+
+    let dataset = load_dataset(file.path().to_str().unwrap()).unwrap();
+    let categories = &dataset.categories;
+    assert_eq!(categories.breadcrumbs("A"), vec!["Books > Subjects > Cooking", "Books > Specialty > Cooking"]);
+    assert_eq!(categories.breadcrumbs("B"), vec!["Books > Specialty > Cooking"]);
+    assert_eq!(categories.products_under(1000), vec!["A"]);
+    assert_eq!(categories.products_under(2000), vec!["A", "B"]);
+    assert_eq!(categories.products_under(6), vec!["A", "B"]);
+    assert_eq!(categories.parents(6), vec![1000, 2000]);
+    assert_eq!(categories.depth(), 3);
+}
+
+#[test]
+fn test_records_parse_reviews() {
+    let data = "ASIN: TEST1
+  title: Test 1
+  reviews: total: 2  downloaded: 2  avg rating: 4.5
+    2000-7-28  cutomer: A2JW67OY8U6HHK  rating: 5  votes:  10  helpful:   9
+    2003-12-14  cutomer: A2VE83MZF98ITY  rating: 4  votes:   6  helpful:   5
+";
+    let parsed: Vec<Product> = records(data.as_bytes()).collect::<Result<_, _>>().unwrap();
+    let product = &parsed[0];
+
+    assert_eq!(product.review_summary.total, 2);
+    assert_eq!(product.review_summary.downloaded, 2);
+    assert_eq!(product.review_summary.avg_rating, 4.5);
+    assert_eq!(product.reviews.len(), 2);
+    assert_eq!(product.reviews[0].date, ReviewDate { year: 2000, month: 7, day: 28 });
+    assert_eq!(product.reviews[0].customer, "A2JW67OY8U6HHK");
+    assert_eq!(product.reviews[1].rating, 4);
+    assert_eq!(product.reviews[1].votes, 6);
+    assert_eq!(product.reviews[1].helpful, 5);
+}
+
+#[test]
+fn test_load_dataset_indexes_ids() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Total items: 2\n\nId:   1\nASIN: TEST1\n  title: Test 1\n  similar: 1  TEST2").unwrap();
+    writeln!(file, "\nId:   7\nASIN: TEST2\n  title: Test 2\n  similar: 0").unwrap();
+    file.flush().unwrap();
+
+    let dataset = load_dataset(file.path().to_str().unwrap()).unwrap();
+    assert_eq!(dataset.products["TEST1"].id, Some(1));
+    assert_eq!(dataset.ids.asin(7), Some("TEST2"));
+    assert_eq!(dataset.ids.id("TEST1"), Some(1));
+    assert_eq!(dataset.product_by_id(7).unwrap().title, "Test 2");
+    assert!(dataset.product_by_id(3).is_none());
+}
+
+#[test]
+fn test_id_index_stays_two_way() {
+    let mut ids = IdIndex::default();
+    ids.insert(1, "A");
+    ids.insert(1, "B");
+    assert_eq!(ids.asin(1), Some("B"));
+    assert_eq!(ids.id("A"), None);
+
+    ids.insert(2, "B");
+    assert_eq!(ids.asin(1), None);
+    assert_eq!(ids.id("B"), Some(2));
+    assert_eq!(ids.len(), 1);
+}
+
+#[test]
+fn test_duplicate_ids_are_reported() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Id:   1\nASIN: TEST1\n  title: Test 1\n\nId:   1\nASIN: TEST2\n  title: Test 2").unwrap();
+    file.flush().unwrap();
+    let path = file.path().to_str().unwrap();
+
+    let (dataset, report) = load_dataset_with_options(path, &LoadOptions::default()).unwrap();
+    assert_eq!(dataset.products.len(), 2);
+    assert_eq!(dataset.ids.asin(1), Some("TEST1"));
+    assert_eq!(dataset.ids.id("TEST2"), None);
+    assert_eq!(report.duplicate_ids.len(), 1);
+    assert_eq!((report.duplicate_ids[0].line_number, report.duplicate_ids[0].field), (5, "Id"));
+    assert!(report.duplicate_asins.is_empty());
+
+    let strict = LoadOptions { mode: ParseMode::Strict, ..Default::default() };
+    let err = load_dataset_with_options(path, &strict).err().unwrap();
+    assert!(matches!(err, LoaderError::MalformedField { line_number: 5, field: "Id", .. }));
+}
+
+#[test]
+fn test_load_dataset_counts_discontinued() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Id:   1\nASIN: TEST1\n  title: Test 1\n  similar: 0").unwrap();
+    writeln!(file, "\nId:   2\nASIN: TEST2\n  discontinued product").unwrap();
+    file.flush().unwrap();
+
+    let dataset = load_dataset(file.path().to_str().unwrap()).unwrap();
+    assert_eq!(dataset.discontinued, 1);
+    assert_eq!(dataset.products["TEST1"].status, ProductStatus::Active);
+    assert_eq!(dataset.products["TEST2"].status, ProductStatus::Discontinued);
+}
+
+#[test]
+fn test_loader_errors_report_line() {
+    let strict = LoadOptions { mode: ParseMode::Strict, ..Default::default() };
+    let data = "Id:   1\nASIN: TEST1\n  title: Test 1\n\nId:   x2\nASIN: TEST2\n";
+    let err = records_with_options(data.as_bytes(), &strict).find_map(Result::err).unwrap();
+    assert!(matches!(err, LoaderError::BadInteger { field: "Id", .. }));
+    assert_eq!(err.line_number(), 5);
+    assert_eq!(err.line(), "Id:   x2");
+
+    let data = "Id:   1\nASIN: TEST1\n\nId:   2\n  discontinued product\n";
+    let err = records_with_options(data.as_bytes(), &strict).find_map(Result::err).unwrap();
+    assert!(matches!(err, LoaderError::TruncatedRecord { line_number: 4, .. }));
+
+    let data = "Id:   1\nASIN:\n";
+    let err = records_with_options(data.as_bytes(), &strict).find_map(Result::err).unwrap();
+    assert!(matches!(err, LoaderError::MalformedField { line_number: 2, field: "ASIN", .. }));
+
+    let err = load_dataset("does/not/exist.txt").err().unwrap();
+    assert!(matches!(err, LoaderError::Io { line_number: 0, .. }));
+}
+
+#[test]
+fn test_lenient_and_strict_modes() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Id:   1\nASIN: TEST1\n  title: Test 1\n  salesrank: n/a\n  similar: 2  TEST2  GONE1").unwrap();
+    writeln!(file, "  mystery: field\n\nId:   2\nASIN: TEST2\n  title: Test 2\n  similar: 0").unwrap();
+    writeln!(file, "\nId:   3\nASIN: TEST2\n  title: Test 2 again\n  similar: 0").unwrap();
+    file.flush().unwrap();
+    let path = file.path().to_str().unwrap();
+
+    let lenient = LoadOptions { mode: ParseMode::Lenient, ..Default::default() };
+    let (dataset, report) = load_dataset_with_options(path, &lenient).unwrap();
+    assert_eq!(dataset.products["TEST1"].salesrank, -1);
+    assert_eq!(dataset.products["TEST2"].title, "Test 2");
+    assert_eq!(report.defaulted_fields.len(), 1);
+    assert_eq!(report.defaulted_fields[0].field, "salesrank");
+    assert_eq!(report.skipped_lines.len(), 1);
+    assert_eq!(report.skipped_lines[0].line_number, 6);
+    assert_eq!(report.duplicate_asins.len(), 1);
+    assert_eq!(report.duplicate_asins[0].line_number, 13);
+    assert_eq!(report.dangling_similar, vec!["GONE1".to_string()]);
+
+    let strict = LoadOptions { mode: ParseMode::Strict, ..Default::default() };
+    let err = load_dataset_with_options(path, &strict).err().unwrap();
+    assert!(matches!(err, LoaderError::BadInteger { line_number: 4, field: "salesrank", .. }));
+}
+
+#[test]
+fn test_declared_counts_are_checked() {
+    let strict = LoadOptions { mode: ParseMode::Strict, ..Default::default() };
+    let data = "Id:   1\nASIN: TEST1\n  similar: 5  TEST2  TEST3\n";
+    let err = records_with_options(data.as_bytes(), &strict).find_map(Result::err).unwrap();
+    assert!(matches!(err, LoaderError::MalformedField { line_number: 3, field: "similar", .. }));
+
+    let data = "Id:   1\nASIN: TEST1\n  similar: x  TEST2\n";
+    let err = records_with_options(data.as_bytes(), &strict).find_map(Result::err).unwrap();
+    assert!(matches!(err, LoaderError::BadInteger { line_number: 3, field: "similar", .. }));
+
+    let data = "Id:   1\nASIN: TEST1\n  categories: 3\n   |Books[283155]\n\nId:   2\nASIN: TEST2\n";
+    let err = records_with_options(data.as_bytes(), &strict).find_map(Result::err).unwrap();
+    assert!(matches!(err, LoaderError::MalformedField { line_number: 3, field: "categories", .. }));
+
+    let data = "Id:   1\nASIN: TEST1\n  similar: 5  TEST2  TEST3\n  categories: 3\n   |Books[283155]\n\n\
+                Id:   2\nASIN: TEST2\n  categories: x\n";
+    let mut stream = records(data.as_bytes());
+    let parsed: Vec<Product> = stream.by_ref().collect::<Result<_, _>>().unwrap();
+    assert_eq!(parsed[0].similar, ["TEST2", "TEST3"]);
+    assert_eq!(parsed[0].categories.len(), 1);
+
+    let report = stream.into_report();
+    let defaulted: Vec<_> = report.defaulted_fields.iter().map(|i| (i.line_number, i.field)).collect();
+    assert_eq!(defaulted, [(3, "similar"), (4, "categories"), (9, "categories")]);
+}
+
+#[test]
+fn test_lenient_mode_keeps_going_past_broken_records() {
+    let data = "Id:   1x\nASIN: TEST1\n  title: Test 1\n\nId:   2\nASIN:\n  title: No ASIN\n\nId:   3\nASIN: TEST3\n";
+    let mut stream = records(data.as_bytes());
+    let parsed: Vec<Product> = stream.by_ref().collect::<Result<_, _>>().unwrap();
+    let asins: Vec<_> = parsed.iter().map(|p| p.asin.as_str()).collect();
+    assert_eq!(asins, ["TEST1", "TEST3"]);
+    assert_eq!(parsed[0].id, None);
+
+    let report = stream.into_report();
+    let defaulted: Vec<_> = report.defaulted_fields.iter().map(|i| (i.line_number, i.field)).collect();
+    assert_eq!(defaulted, [(1, "Id")]);
+    let skipped: Vec<_> = report.skipped_lines.iter().map(|i| (i.line_number, i.field)).collect();
+    assert_eq!(skipped, [(6, "ASIN"), (5, "ASIN")]);
+}
+
+#[test]
+fn test_duplicate_records_add_no_links() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Id:   1\nASIN: A\n  similar: 1  B\n\nId:   2\nASIN: A\n  similar: 1  C").unwrap();
+    file.flush().unwrap();
+
+    let (dataset, report) = load_dataset_with_options(file.path().to_str().unwrap(), &LoadOptions::default()).unwrap();
+    assert_eq!(report.duplicate_asins.len(), 1);
+    assert_eq!(dataset.products["A"].similar, ["B"]);
+    let asins: Vec<_> = dataset.graph.node_indices().map(|n| dataset.graph[n].as_str()).collect();
+    assert_eq!(asins, ["A", "B"]);
+    assert_eq!(dataset.graph.edge_count(), 1);
+}
+
+#[test]
+fn test_products_without_similar_line_are_loaded() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "Id:   1\nASIN: TEST1\n  title: Test 1\n  group: Book\n  salesrank: 5\n").unwrap();
+    writeln!(file, "Id:   2\nASIN: TEST2\n  title: Test 2\n  similar: 1  TEST1").unwrap();
+    file.flush().unwrap();
+
+    let dataset = load_dataset(file.path().to_str().unwrap()).unwrap();
+    assert_eq!(dataset.products.len(), 2);
+    assert_eq!(dataset.products["TEST1"].salesrank, 5);
+    assert_eq!(dataset.graph.node_count(), 2);
+    assert_eq!(dataset.graph.edge_count(), 1);
+}
+
+#[test]
+fn test_load_gzip_dump() {
+    let file = NamedTempFile::new().unwrap();
+    let mut encoder = GzEncoder::new(file.reopen().unwrap(), flate2::Compression::default());
+    writeln!(encoder, "Id:   1\nASIN: TEST1\n  title: Test 1\n  similar: 1  TEST2\n").unwrap();
+    writeln!(encoder, "Id:   2\nASIN: TEST2\n  title: Test 2\n  similar: 0").unwrap();
+    encoder.finish().unwrap();
+
+    let dataset = load_dataset(file.path().to_str().unwrap()).unwrap();
+    assert_eq!(dataset.products.len(), 2);
+    assert_eq!(dataset.graph.edge_count(), 1);
+
+    let mut bzip = NamedTempFile::new().unwrap();
+    bzip.write_all(b"BZh91AY&SY").unwrap();
+    let err = load_dataset(bzip.path().to_str().unwrap()).err().unwrap();
+    assert!(matches!(err, LoaderError::Io { .. }));
+}
+
+#[test]
+fn test_parallel_load_matches_sequential() {
+    // Big enough to be cut into several chunks, with a duplicate and bad fields spread around
+    let mut file = NamedTempFile::new().unwrap();
+    for i in 0..20_000 {
+        let asin = if i == 15_000 { "P00007".to_string() } else { format!("P{:05}", i) };
+        writeln!(file, "Id:   {}\nASIN: {}", i, asin).unwrap();
+        writeln!(file, "  title: Product number {} with a reasonably long title", i).unwrap();
+        if i % 997 == 0 {
+            writeln!(file, "  salesrank: unknown").unwrap();
+        }
+        writeln!(file, "  similar: 2  P{:05}  X{:05}\n", (i * 7919) % 20_000, i).unwrap();
+    }
+    let path = file.path().to_str().unwrap();
+
+    let graph_of = |threads| {
+        let options = LoadOptions { threads, quiet: true, ..Default::default() };
+        let (dataset, report) = load_dataset_with_options(path, &options).unwrap();
+        let nodes: Vec<String> = dataset.graph.node_weights().cloned().collect();
+        let edges: Vec<(usize, usize)> = dataset.graph.edge_indices()
+            .map(|e| dataset.graph.edge_endpoints(e).unwrap())
+            .map(|(a, b)| (a.index(), b.index()))
+            .collect();
+        (nodes, edges, dataset.products.len(), report)
+    };
+
+    let (nodes, edges, products, report) = graph_of(1);
+    assert_eq!(products, 19_999);
+    assert_eq!(report.duplicate_asins.len(), 1);
+    for threads in [2, 4] {
+        let (parallel_nodes, parallel_edges, parallel_products, parallel_report) = graph_of(threads);
+        assert_eq!(parallel_nodes, nodes);
+        assert_eq!(parallel_edges, edges);
+        assert_eq!(parallel_products, products);
+        assert_eq!(parallel_report.duplicate_asins[0].line_number, report.duplicate_asins[0].line_number);
+        assert_eq!(parallel_report.skipped_lines.len(), report.skipped_lines.len());
+        assert_eq!(parallel_report.defaulted_fields.len(), report.defaulted_fields.len());
+        assert_eq!(parallel_report.dangling_similar, report.dangling_similar);
+    }
+
+    // Strict mode stops at the same line either way
+    let strict_error = |threads| {
+        let options = LoadOptions { mode: ParseMode::Strict, threads, quiet: true, ..Default::default() };
+        load_dataset_with_options(path, &options).err().unwrap().line_number()
+    };
+    assert_eq!(strict_error(4), strict_error(1));
+}
+
+// Helper function for other tests
+pub fn create_test_dataset() -> ProductDataset {
+    let mut graph = Graph::new();
+    let mut products = HashMap::new();
+    
+    let node1 = graph.add_node("TEST1".to_string());
+    let node2 = graph.add_node("TEST2".to_string());
+    graph.add_edge(node1, node2, SimilarLink::default());
+    
+    products.insert("TEST1".to_string(), 
+        Product {
+            asin: "TEST1".to_string(),
+            title: "Test Product 1".to_string(),
+            group: "Book".to_string(),
+            salesrank: 100,
+            ..Default::default()
+        }
+    );
+    
+    products.insert("TEST2".to_string(), 
+        Product {
+            asin: "TEST2".to_string(),
+            title: "Test Product 2".to_string(),
+            group: "Book".to_string(),
+            salesrank: 200,
+            ..Default::default()
+        }
+    );
+    
+    ProductDataset { graph, products, ..Default::default() }
+}
\ No newline at end of file
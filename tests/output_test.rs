@@ -0,0 +1,103 @@
+use amazon_trends::analyzer::ProductAnalyzer;
+use amazon_trends::loader::{Product, ProductDataset, SimilarLink};
+use amazon_trends::output::{self, write_rows, OutputFormat, StatsSummary};
+use petgraph::graph::Graph;
+
+#[test]
+fn test_ranked_products_in_every_format() {
+    let first = product("PROD1", 100);
+    let second = product("PROD2", 200);
+    let rows = output::ranked_products(&[&first, &second]);
+
+    let mut json = Vec::new();
+    write_rows(&rows, OutputFormat::Json, &mut json).unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(parsed[1]["rank"], 2);
+    assert_eq!(parsed[1]["asin"], "PROD2");
+
+    let mut jsonl = Vec::new();
+    write_rows(&rows, OutputFormat::JsonLines, &mut jsonl).unwrap();
+    assert_eq!(String::from_utf8(jsonl).unwrap().lines().count(), 2);
+
+    let mut csv = Vec::new();
+    write_rows(&rows, OutputFormat::Csv, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "rank,asin,id,title,group,salesrank");
+    assert_eq!(lines[1], "1,PROD1,,Test PROD1,Book,100");
+}
+
+#[test]
+fn test_scored_products_carry_the_score() {
+    let first = product("PROD1", 100);
+    let second = product("PROD2", 200);
+    let rows = output::scored_products(&[(&first, 0.75), (&second, 0.25)]);
+    assert_eq!((rows[1].rank, rows[1].score), (2, 0.25));
+
+    let mut csv = Vec::new();
+    write_rows(&rows, OutputFormat::Csv, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "rank,asin,id,title,group,salesrank,score");
+    assert_eq!(lines[1], "1,PROD1,,Test PROD1,Book,100,0.75");
+}
+
+#[test]
+fn test_cluster_members_are_flattened() {
+    let first = product("PROD1", 100);
+    let second = product("PROD2", 200);
+    let third = product("PROD3", 300);
+    let rows = output::cluster_members(&[vec![&first, &second], vec![&third]]);
+
+    assert_eq!(rows.len(), 3);
+    assert_eq!((rows[1].cluster, rows[1].cluster_size), (1, 2));
+    assert_eq!((rows[2].cluster, rows[2].asin.as_str()), (2, "PROD3"));
+}
+
+#[test]
+fn test_dataset_stats_include_graph_and_components() {
+    // PROD1 -> PROD2, plus a lone PROD3
+    let mut graph = Graph::new();
+    let nodes: Vec<_> = ["PROD1", "PROD2", "PROD3"].iter().map(|asin| graph.add_node(asin.to_string())).collect();
+    graph.add_edge(nodes[0], nodes[1], SimilarLink::default());
+    let products = ["PROD1", "PROD2", "PROD3"].iter()
+        .map(|asin| (asin.to_string(), product(asin, 100)))
+        .collect();
+    let dataset = ProductDataset { graph, products, ..Default::default() };
+    let analyzer = ProductAnalyzer::new(&dataset);
+    let stats = output::dataset_stats(&dataset, &analyzer);
+
+    let mut json = Vec::new();
+    write_rows(&[&stats], OutputFormat::Json, &mut json).unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(parsed[0]["summary"]["products"], 3);
+    assert_eq!(parsed[0]["graph"]["links"], 1);
+    assert_eq!(parsed[0]["components"], 2);
+    assert_eq!(parsed[0]["component_sizes"]["2"], 1);
+    assert_eq!(parsed[0]["giant_component"]["nodes"], 2);
+    assert_eq!(parsed[0]["giant_component"]["groups"][0][1], 2);
+
+    let mut csv = Vec::new();
+    write_rows(&[StatsSummary::from(&stats)], OutputFormat::Csv, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with(",components,giant_nodes,giant_edges,giant_node_share,giant_edge_share"));
+    assert!(lines[1].ends_with(",2,2,1,0.6666666666666666,1.0"));
+}
+
+#[test]
+fn test_output_format_from_str() {
+    assert_eq!("jsonl".parse::<OutputFormat>().unwrap(), OutputFormat::JsonLines);
+    assert!("xml".parse::<OutputFormat>().is_err());
+}
+
+fn product(asin: &str, salesrank: i32) -> Product {
+    Product {
+        asin: asin.to_string(),
+        title: format!("Test {}", asin),
+        group: "Book".to_string(),
+        salesrank,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,113 @@
+use amazon_trends::loader::{load_dataset, LoadOptions, LoaderError, ParseMode, ProductDataset};
+use amazon_trends::snapshot::{self, SnapshotError, SNAPSHOT_VERSION};
+use std::io::Write;
+use std::time::{Duration, SystemTime};
+use tempfile::NamedTempFile;
+
+#[test]
+fn test_snapshot_round_trip() {
+    let source = write_dump(2);
+    let dataset = load_dataset(path(&source)).unwrap();
+    let snapshot = NamedTempFile::new().unwrap();
+    dataset.save(path(&snapshot)).unwrap();
+
+    let restored = ProductDataset::load_snapshot_for(path(&snapshot), path(&source)).unwrap();
+    assert_eq!(restored.products.len(), dataset.products.len());
+    assert_eq!(restored.graph.node_count(), dataset.graph.node_count());
+    assert_eq!(restored.graph.edge_count(), dataset.graph.edge_count());
+    let links = |d: &ProductDataset| d.graph.edge_weights().copied().collect::<Vec<_>>();
+    assert_eq!(links(&restored), links(&dataset));
+    assert_eq!(restored.source_hash, dataset.source_hash);
+    assert!(restored.source_stamp.is_some());
+    assert_eq!(restored.source_stamp, dataset.source_stamp);
+    assert_eq!(restored.ids.asin(2), Some("TEST2"));
+    assert_eq!(restored.categories.breadcrumbs("TEST1"), dataset.categories.breadcrumbs("TEST1"));
+
+    let original = &dataset.products["TEST1"];
+    let product = &restored.products["TEST1"];
+    assert_eq!(product.title, original.title);
+    assert_eq!(product.similar, original.similar);
+    assert_eq!(product.categories, original.categories);
+    assert_eq!(product.reviews, original.reviews);
+    assert_eq!(product.review_summary, original.review_summary);
+}
+
+#[test]
+fn test_stale_snapshot_is_detected() {
+    let source = write_dump(2);
+    let snapshot = NamedTempFile::new().unwrap();
+    load_dataset(path(&source)).unwrap().save(path(&snapshot)).unwrap();
+
+    let changed = write_dump(3);
+    let err = ProductDataset::load_snapshot_for(path(&snapshot), path(&changed)).err().unwrap();
+    assert!(matches!(err, SnapshotError::Stale { .. }));
+}
+
+#[test]
+fn test_touched_source_falls_back_to_hash() {
+    let source = write_dump(2);
+    let snapshot = NamedTempFile::new().unwrap();
+    load_dataset(path(&source)).unwrap().save(path(&snapshot)).unwrap();
+
+    // Same bytes under a new modification time: the stamp differs but the hash still matches
+    let touched = SystemTime::now() + Duration::from_secs(60);
+    source.as_file().set_modified(touched).unwrap();
+    let restored = ProductDataset::load_snapshot_for(path(&snapshot), path(&source)).unwrap();
+    assert_eq!(restored.products.len(), 2);
+}
+
+#[test]
+fn test_rejects_non_snapshot() {
+    let source = write_dump(2);
+    let err = ProductDataset::load_snapshot(path(&source)).err().unwrap();
+    assert!(matches!(err, SnapshotError::BadMagic));
+}
+
+#[test]
+fn test_corrupt_counts_are_rejected() {
+    let mut snapshot = NamedTempFile::new().unwrap();
+    snapshot.write_all(b"AMZSNAP\0").unwrap();
+    snapshot.write_all(&SNAPSHOT_VERSION.to_le_bytes()).unwrap();
+    snapshot.write_all(&[0; 9]).unwrap(); // No source hash
+    snapshot.write_all(&[0; 21]).unwrap(); // No source stamp
+    snapshot.write_all(&0u64.to_le_bytes()).unwrap();
+    snapshot.write_all(&[0, 0, 1]).unwrap(); // Graph mode, unresolved nodes, parse mode
+    snapshot.write_all(&u64::MAX.to_le_bytes()).unwrap(); // Product count
+    snapshot.flush().unwrap();
+
+    let err = ProductDataset::load_snapshot(path(&snapshot)).err().unwrap();
+    assert!(matches!(err, SnapshotError::Corrupt(_)));
+}
+
+#[test]
+fn test_lenient_snapshot_is_not_used_for_strict_loads() {
+    let mut source = write_dump(2);
+    writeln!(source, "Id:   3\nASIN: TEST3\n  salesrank: n/a").unwrap();
+    source.flush().unwrap();
+    let snapshot = NamedTempFile::new().unwrap();
+
+    let lenient = LoadOptions { mode: ParseMode::Lenient, quiet: true, ..Default::default() };
+    let dataset = snapshot::load_cached(path(&source), path(&snapshot), &lenient).unwrap();
+    assert_eq!(dataset.parse_mode, ParseMode::Lenient);
+    assert_eq!(ProductDataset::load_snapshot(path(&snapshot)).unwrap().parse_mode, ParseMode::Lenient);
+
+    let strict = LoadOptions { mode: ParseMode::Strict, quiet: true, ..Default::default() };
+    let err = snapshot::load_cached(path(&source), path(&snapshot), &strict).err().unwrap();
+    assert!(matches!(err, LoaderError::BadInteger { field: "salesrank", .. }));
+}
+
+fn write_dump(products: u32) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    for i in 1..=products {
+        writeln!(file, "Id:   {}\nASIN: TEST{}\n  title: Test {}\n  salesrank: {}", i, i, i, i * 100).unwrap();
+        writeln!(file, "  similar: 1  TEST{}\n  categories: 1\n   |Books[283155]|Subjects[1000]", i % products + 1).unwrap();
+        writeln!(file, "  reviews: total: 1  downloaded: 1  avg rating: 4").unwrap();
+        writeln!(file, "    2001-3-4  cutomer: C{}  rating: 4  votes: 2  helpful: 1\n", i).unwrap();
+    }
+    file.flush().unwrap();
+    file
+}
+
+fn path(file: &NamedTempFile) -> &str {
+    file.path().to_str().unwrap()
+}